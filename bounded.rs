@@ -0,0 +1,125 @@
+//! Byte strings bounded by the limits of the runtime.
+//!
+//! The runtime rejects metadata, attribute keys and values exceeding the `StringLimit`,
+//! `KeyLimit` and `ValueLimit` of its NFT pallet, and memos exceeding the `MaxMemoLen` of
+//! the extension. Taking these types instead of plain `Vec<u8>`s moves that check into
+//! the contract: arrays that are too long fail to compile, and vectors that are too long
+//! fail to convert before any call is made.
+//!
+//! A contract cannot read the limits of the runtime when it is compiled, so the
+//! constants below copy them and have to be kept in sync by hand. They match the mock
+//! runtime of the pallet, `runtime/src/mock.rs`. A bound above the runtime's only gives
+//! up the check in the contract; the runtime still rejects the input.
+//!
+//! Both types encode exactly like `Vec<u8>`.
+
+use ink::prelude::vec::Vec;
+
+/// The `StringLimit` of the runtime's NFT pallet.
+pub const STRING_LIMIT: usize = 50;
+/// The `KeyLimit` of the runtime's NFT pallet.
+pub const KEY_LIMIT: usize = 50;
+/// The `ValueLimit` of the runtime's NFT pallet.
+pub const VALUE_LIMIT: usize = 50;
+/// The `MaxMemoLen` of the extension's `Config`.
+pub const MEMO_LIMIT: usize = 16;
+
+/// Metadata of an item, bounded by `StringLimit`.
+pub type Metadata = BoundedData<STRING_LIMIT>;
+/// The key of an attribute, bounded by `KeyLimit`.
+pub type AttributeKey = BoundedKey<KEY_LIMIT>;
+/// The value of an attribute, bounded by `ValueLimit`.
+pub type AttributeValue = BoundedData<VALUE_LIMIT>;
+/// The memo of an item, bounded by `MaxMemoLen`.
+pub type Memo = BoundedData<MEMO_LIMIT>;
+
+/// Fails to compile when an array of `LEN` bytes is converted to a type bounded by `MAX`.
+struct AssertFits<const LEN: usize, const MAX: usize>;
+
+impl<const LEN: usize, const MAX: usize> AssertFits<LEN, MAX> {
+    const OK: () = assert!(LEN <= MAX, "the array exceeds the bound of the type");
+}
+
+macro_rules! bounded_bytes {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(scale::Encode, Clone, Debug, Default, PartialEq, Eq)]
+        #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+        pub struct $name<const N: usize>(Vec<u8>);
+
+        impl<const N: usize> $name<N> {
+            /// The maximum number of bytes.
+            pub const MAX_LEN: usize = N;
+
+            /// Takes `bytes`, checking at compile time that they fit.
+            pub fn from_array<const LEN: usize>(bytes: [u8; LEN]) -> Self {
+                #[allow(clippy::let_unit_value)]
+                let () = AssertFits::<LEN, N>::OK;
+                Self(bytes.into())
+            }
+
+            /// Returns the bytes.
+            pub fn as_slice(&self) -> &[u8] {
+                &self.0
+            }
+
+            /// Returns the bytes, giving up the bound.
+            pub fn into_inner(self) -> Vec<u8> {
+                self.0
+            }
+        }
+
+        impl<const N: usize> TryFrom<Vec<u8>> for $name<N> {
+            type Error = Vec<u8>;
+
+            /// Takes `bytes`, or hands them back if they exceed `N`.
+            fn try_from(bytes: Vec<u8>) -> core::result::Result<Self, Self::Error> {
+                if bytes.len() <= N {
+                    Ok(Self(bytes))
+                } else {
+                    Err(bytes)
+                }
+            }
+        }
+
+        impl<const N: usize> scale::Decode for $name<N> {
+            fn decode<I: scale::Input>(input: &mut I) -> core::result::Result<Self, scale::Error> {
+                Self::try_from(Vec::<u8>::decode(input)?)
+                    .map_err(|_| concat!(stringify!($name), " exceeds its bound").into())
+            }
+        }
+    };
+}
+
+bounded_bytes! {
+    /// Data of at most `N` bytes, e.g. metadata or an attribute value.
+    BoundedData
+}
+
+bounded_bytes! {
+    /// An attribute key of at most `N` bytes.
+    BoundedKey
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::{
+        Decode,
+        Encode,
+    };
+
+    #[test]
+    fn encodes_like_vec() {
+        let data = BoundedData::<4>::from_array(*b"abc");
+        assert_eq!(data.encode(), b"abc".to_vec().encode());
+        assert_eq!(BoundedData::<4>::decode(&mut &data.encode()[..]), Ok(data));
+    }
+
+    #[test]
+    fn rejects_bytes_exceeding_the_bound() {
+        assert_eq!(BoundedKey::<2>::try_from(vec![1, 2, 3]), Err(vec![1, 2, 3]));
+        assert!(BoundedKey::<2>::try_from(vec![1, 2]).is_ok());
+        assert!(BoundedKey::<2>::decode(&mut &vec![1u8, 2, 3].encode()[..]).is_err());
+    }
+}
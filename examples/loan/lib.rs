@@ -0,0 +1,371 @@
+//! Loans of native tokens collateralized by NFTs, on top of the PSP-02 chain extension.
+//!
+//! The lender deploys and funds the contract, which lends a fixed amount against any item
+//! for a fixed number of blocks. A borrower first offers an item they own as collateral,
+//! then transfers it to the contract, which holds it in escrow and pays out the loan.
+//! Offering before transferring keeps anyone else from borrowing against the item while
+//! the contract holds it.
+//!
+//! Repaying the loan in time returns the item. Once a loan is overdue, anyone can
+//! liquidate it: the contract approves the lender to take the item, and lists it for the
+//! repayment amount until the lender takes it or somebody buys it, whichever comes first.
+//!
+//! The tests answer the contract's calls with `psp02_extension::replay`, which checks the
+//! order and input of every call, so they double as an integration test of the function
+//! IDs the contract combines.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::env::{
+    DefaultEnvironment,
+    Environment,
+};
+
+/// The default environment with the PSP-02 extension. It cannot be shared with
+/// `psp02_extension::CustomEnvironment`, as ink! ties the events of a contract to the type
+/// of its environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum LoanEnvironment {}
+
+impl Environment for LoanEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+
+    type ChainExtension = psp02_extension::Psp02Extension;
+}
+
+#[ink::contract(env = crate::LoanEnvironment)]
+mod loan {
+    use ink::storage::Mapping;
+    use psp02_extension::Psp02Error;
+
+    #[derive(scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The item does not belong to the caller.
+        NotItemOwner,
+        /// The caller did not offer the item as collateral.
+        NotOffered,
+        /// The contract does not hold the item yet.
+        NotEscrowed,
+        /// There is no running loan against the item.
+        NoLoan,
+        /// Only the borrower can repay a loan.
+        NotBorrower,
+        /// The loan is overdue and can no longer be repaid.
+        Overdue,
+        /// The loan is not overdue yet.
+        NotOverdue,
+        /// The item is not listed.
+        NotListed,
+        /// The transferred value does not match the repayment amount.
+        WrongValue,
+        /// The contract could not pay out the loan.
+        PayoutFailed,
+        /// A call to the chain extension failed.
+        Extension(Psp02Error),
+    }
+
+    impl From<Psp02Error> for Error {
+        fn from(error: Psp02Error) -> Self {
+            Self::Extension(error)
+        }
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The state of the loan against an item.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Loan {
+        /// The borrower offered the item but did not transfer it yet.
+        Offered { borrower: AccountId },
+        /// The contract holds the item until the loan is repaid.
+        Running { borrower: AccountId, due: BlockNumber },
+        /// The loan was not repaid in time and the item is for sale.
+        Listed,
+    }
+
+    /// Emitted whenever a loan is paid out.
+    #[ink(event)]
+    pub struct Borrowed {
+        #[ink(topic)]
+        collection_id: u32,
+        #[ink(topic)]
+        item_id: u32,
+        #[ink(topic)]
+        borrower: AccountId,
+        due: BlockNumber,
+    }
+
+    /// Emitted whenever a loan is repaid and its collateral returned.
+    #[ink(event)]
+    pub struct Repaid {
+        #[ink(topic)]
+        collection_id: u32,
+        #[ink(topic)]
+        item_id: u32,
+    }
+
+    /// Emitted whenever the collateral of an overdue loan is listed.
+    #[ink(event)]
+    pub struct Listed {
+        #[ink(topic)]
+        collection_id: u32,
+        #[ink(topic)]
+        item_id: u32,
+        price: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct Lending {
+        lender: AccountId,
+        /// The amount lent against each item.
+        amount: Balance,
+        /// The amount to repay, which is also the price of liquidated items.
+        repayment: Balance,
+        /// The number of blocks a loan runs.
+        duration: BlockNumber,
+        loans: Mapping<(u32, u32), Loan>,
+    }
+
+    impl Lending {
+        /// Lends `amount` against an item for `duration` blocks, to be repaid with
+        /// `repayment`. The caller becomes the lender and funds the contract with the
+        /// transferred value.
+        #[ink(constructor, payable)]
+        pub fn new(amount: Balance, repayment: Balance, duration: BlockNumber) -> Self {
+            Self {
+                lender: Self::env().caller(),
+                amount,
+                repayment,
+                duration,
+                loans: Mapping::default(),
+            }
+        }
+
+        /// Offers an item of the caller as collateral, to be transferred to this contract
+        /// before calling `borrow`.
+        #[ink(message)]
+        pub fn offer_collateral(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+            let borrower = self.env().caller();
+            if self.env().extension().get_owner(collection_id, item_id)? != Some(borrower) {
+                return Err(Error::NotItemOwner)
+            }
+            self.loans.insert((collection_id, item_id), &Loan::Offered { borrower });
+            Ok(())
+        }
+
+        /// Pays out the loan against an offered item the contract holds by now.
+        #[ink(message)]
+        pub fn borrow(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+            let borrower = self.env().caller();
+            if self.loans.get((collection_id, item_id)) != Some(Loan::Offered { borrower }) {
+                return Err(Error::NotOffered)
+            }
+            let owner = self.env().extension().get_owner(collection_id, item_id)?;
+            if owner != Some(self.env().account_id()) {
+                return Err(Error::NotEscrowed)
+            }
+            let due = self.env().block_number().saturating_add(self.duration);
+            self.loans.insert((collection_id, item_id), &Loan::Running { borrower, due });
+            self.env().transfer(borrower, self.amount).map_err(|_| Error::PayoutFailed)?;
+            self.env().emit_event(Borrowed { collection_id, item_id, borrower, due });
+            Ok(())
+        }
+
+        /// Repays the caller's loan against an item and returns the item to them.
+        #[ink(message, payable)]
+        pub fn repay(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+            let (borrower, due) = match self.loans.get((collection_id, item_id)) {
+                Some(Loan::Running { borrower, due }) => (borrower, due),
+                _ => return Err(Error::NoLoan),
+            };
+            if self.env().caller() != borrower {
+                return Err(Error::NotBorrower)
+            }
+            if self.env().block_number() > due {
+                return Err(Error::Overdue)
+            }
+            if self.env().transferred_value() != self.repayment {
+                return Err(Error::WrongValue)
+            }
+            self.env().extension().transfer(collection_id, item_id, borrower)?;
+            self.loans.remove((collection_id, item_id));
+            self.env().emit_event(Repaid { collection_id, item_id });
+            Ok(())
+        }
+
+        /// Approves the lender to take the collateral of an overdue loan and lists it
+        /// for the repayment amount in the meantime.
+        #[ink(message)]
+        pub fn liquidate(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+            match self.loans.get((collection_id, item_id)) {
+                Some(Loan::Running { due, .. }) if self.env().block_number() > due => {}
+                Some(Loan::Running { .. }) => return Err(Error::NotOverdue),
+                _ => return Err(Error::NoLoan),
+            }
+            self.env().extension().approve(collection_id, item_id, self.lender)?;
+            self.loans.insert((collection_id, item_id), &Loan::Listed);
+            self.env().emit_event(Listed { collection_id, item_id, price: self.repayment });
+            Ok(())
+        }
+
+        /// Returns the price of a listed item, if it is listed.
+        #[ink(message)]
+        pub fn price(&self, collection_id: u32, item_id: u32) -> Option<Balance> {
+            (self.loans.get((collection_id, item_id)) == Some(Loan::Listed))
+                .then_some(self.repayment)
+        }
+
+        /// Buys a listed item, unless the lender took it already.
+        #[ink(message, payable)]
+        pub fn buy(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+            if self.loans.get((collection_id, item_id)) != Some(Loan::Listed) {
+                return Err(Error::NotListed)
+            }
+            if self.env().transferred_value() != self.repayment {
+                return Err(Error::WrongValue)
+            }
+            let owner = self.env().extension().get_owner(collection_id, item_id)?;
+            if owner != Some(self.env().account_id()) {
+                return Err(Error::NotListed)
+            }
+            // Transferring the item also clears the approval of the lender.
+            let buyer = self.env().caller();
+            self.env().extension().transfer(collection_id, item_id, buyer)?;
+            self.loans.remove((collection_id, item_id));
+            Ok(())
+        }
+
+        /// Returns the loan against an item, if there is one.
+        #[ink(message)]
+        pub fn loan(&self, collection_id: u32, item_id: u32) -> Option<Loan> {
+            self.loans.get((collection_id, item_id))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::LoanEnvironment as Env;
+        use ink::env::test;
+        use psp02_extension::replay::{
+            RecordedCall,
+            Replay,
+        };
+
+        const COLLECTION: u32 = 0;
+        const ITEM: u32 = 42;
+        const AMOUNT: Balance = 100;
+        const REPAYMENT: Balance = 110;
+        const DURATION: BlockNumber = 10;
+
+        fn accounts() -> test::DefaultAccounts<Env> {
+            test::default_accounts::<Env>()
+        }
+
+        /// Deploys the contract as the lender, Alice, and funds it with `AMOUNT`.
+        fn deploy() -> Lending {
+            test::set_caller::<Env>(accounts().alice);
+            let contract = Lending::new(AMOUNT, REPAYMENT, DURATION);
+            test::set_account_balance::<Env>(test::callee::<Env>(), AMOUNT);
+            contract
+        }
+
+        /// The calls of Bob offering `ITEM` and borrowing against it.
+        fn borrow_calls() -> Vec<RecordedCall> {
+            vec![
+                RecordedCall::new(0x162d, (COLLECTION, ITEM), Some(accounts().bob)),
+                RecordedCall::new(0x162d, (COLLECTION, ITEM), Some(test::callee::<Env>())),
+            ]
+        }
+
+        /// Lets Bob offer `ITEM` and borrow against it.
+        fn borrow(contract: &mut Lending) {
+            test::set_caller::<Env>(accounts().bob);
+            assert!(contract.offer_collateral(COLLECTION, ITEM).is_ok());
+            assert!(contract.borrow(COLLECTION, ITEM).is_ok());
+        }
+
+        #[ink::test]
+        fn repaying_returns_the_collateral() {
+            let bob = accounts().bob;
+            let mut calls = vec![RecordedCall::new(0x162d, (COLLECTION, ITEM), Some(bob))];
+            calls.extend(borrow_calls());
+            calls.push(RecordedCall::new(0xdb20, (COLLECTION, ITEM, bob), ()));
+            let replay = Replay::register(calls);
+            let mut contract = deploy();
+            let balance = test::get_account_balance::<Env>(bob).unwrap();
+
+            // Only the owner of an item can offer it.
+            test::set_caller::<Env>(accounts().charlie);
+            let offer = contract.offer_collateral(COLLECTION, ITEM);
+            assert!(matches!(offer, Err(Error::NotItemOwner)));
+            borrow(&mut contract);
+            assert_eq!(test::get_account_balance::<Env>(bob), Ok(balance + AMOUNT));
+            let due = ink::env::block_number::<Env>() + DURATION;
+            assert_eq!(contract.loan(COLLECTION, ITEM), Some(Loan::Running { borrower: bob, due }));
+
+            test::set_value_transferred::<Env>(REPAYMENT - 1);
+            assert!(matches!(contract.repay(COLLECTION, ITEM), Err(Error::WrongValue)));
+            test::set_value_transferred::<Env>(REPAYMENT);
+            assert!(contract.repay(COLLECTION, ITEM).is_ok());
+            assert_eq!(contract.loan(COLLECTION, ITEM), None);
+            assert_eq!(test::recorded_events().count(), 2);
+            replay.assert_finished();
+        }
+
+        #[ink::test]
+        fn overdue_collateral_is_listed() {
+            let mut calls = borrow_calls();
+            calls.extend([
+                RecordedCall::new(0x1932, (COLLECTION, ITEM, accounts().alice), ()),
+                RecordedCall::new(0x162d, (COLLECTION, ITEM), Some(test::callee::<Env>())),
+                RecordedCall::new(0xdb20, (COLLECTION, ITEM, accounts().charlie), ()),
+            ]);
+            let replay = Replay::register(calls);
+            let mut contract = deploy();
+            borrow(&mut contract);
+
+            assert!(matches!(contract.liquidate(COLLECTION, ITEM), Err(Error::NotOverdue)));
+            for _ in 0..=DURATION {
+                test::advance_block::<Env>();
+            }
+            test::set_value_transferred::<Env>(REPAYMENT);
+            assert!(matches!(contract.repay(COLLECTION, ITEM), Err(Error::Overdue)));
+            assert!(contract.liquidate(COLLECTION, ITEM).is_ok());
+            assert_eq!(contract.price(COLLECTION, ITEM), Some(REPAYMENT));
+
+            test::set_caller::<Env>(accounts().charlie);
+            assert!(contract.buy(COLLECTION, ITEM).is_ok());
+            assert_eq!(contract.price(COLLECTION, ITEM), None);
+            replay.assert_finished();
+        }
+
+        #[ink::test]
+        fn only_the_offering_owner_can_borrow() {
+            let owner_call = RecordedCall::new(0x162d, (COLLECTION, ITEM), Some(accounts().bob));
+            let replay = Replay::register(vec![owner_call.clone(), owner_call]);
+            let mut contract = deploy();
+
+            test::set_caller::<Env>(accounts().bob);
+            assert!(contract.offer_collateral(COLLECTION, ITEM).is_ok());
+            test::set_caller::<Env>(accounts().charlie);
+            assert!(matches!(contract.borrow(COLLECTION, ITEM), Err(Error::NotOffered)));
+            // The item has to be transferred to the contract first.
+            test::set_caller::<Env>(accounts().bob);
+            assert!(matches!(contract.borrow(COLLECTION, ITEM), Err(Error::NotEscrowed)));
+            replay.assert_finished();
+        }
+    }
+}
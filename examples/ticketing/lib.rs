@@ -0,0 +1,293 @@
+//! Event ticketing on top of the PSP-02 chain extension.
+//!
+//! The contract sells the tickets of a collection it owns, created by the runtime's force
+//! origin with the contract as its owner, issuer and freezer. Tickets are minted straight
+//! to their buyers and can be resold freely until the event. At the door, the organizer
+//! checks a ticket in, which freezes it: the ticket turns into a soulbound proof of
+//! attendance. Holders can return unused tickets for a refund until then, which burns
+//! them.
+//!
+//! The tests answer the contract's calls with `psp02_extension::replay`, which checks the
+//! order and input of every call, so they double as an integration test of the function
+//! IDs the contract combines.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::env::{
+    DefaultEnvironment,
+    Environment,
+};
+
+/// The default environment with the PSP-02 extension. It cannot be shared with
+/// `psp02_extension::CustomEnvironment`, as ink! ties the events of a contract to the type
+/// of its environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TicketingEnvironment {}
+
+impl Environment for TicketingEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = <DefaultEnvironment as Environment>::Balance;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+
+    type ChainExtension = psp02_extension::Psp02Extension;
+}
+
+#[ink::contract(env = crate::TicketingEnvironment)]
+mod ticketing {
+    use psp02_extension::Psp02Error;
+
+    /// The freeze reason of tickets that were checked in.
+    pub const CHECKED_IN: u32 = 1;
+
+    #[derive(scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The transferred value does not match the price of a ticket.
+        WrongPrice,
+        /// Every ticket has been sold.
+        SoldOut,
+        /// Only the organizer can check tickets in.
+        NotOrganizer,
+        /// The ticket does not belong to the account presenting it.
+        NotTicketHolder,
+        /// The ticket was already checked in.
+        TicketVoid,
+        /// The contract could not pay the refund.
+        RefundFailed,
+        /// A call to the chain extension failed.
+        Extension(Psp02Error),
+    }
+
+    impl From<Psp02Error> for Error {
+        fn from(error: Psp02Error) -> Self {
+            Self::Extension(error)
+        }
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Emitted whenever a ticket is sold.
+    #[ink(event)]
+    pub struct TicketSold {
+        #[ink(topic)]
+        item_id: u32,
+        #[ink(topic)]
+        buyer: AccountId,
+    }
+
+    /// Emitted whenever a ticket is checked in at the door.
+    #[ink(event)]
+    pub struct CheckedIn {
+        #[ink(topic)]
+        item_id: u32,
+        #[ink(topic)]
+        holder: AccountId,
+    }
+
+    /// Emitted whenever a ticket is refunded.
+    #[ink(event)]
+    pub struct Refunded {
+        #[ink(topic)]
+        item_id: u32,
+        #[ink(topic)]
+        holder: AccountId,
+    }
+
+    #[ink(storage)]
+    pub struct Ticketing {
+        organizer: AccountId,
+        collection_id: u32,
+        price: Balance,
+        /// The number of tickets left for sale.
+        available: u32,
+    }
+
+    impl Ticketing {
+        /// Sells `supply` tickets of `collection_id` for `price` each, to be checked in by
+        /// the caller.
+        #[ink(constructor)]
+        pub fn new(collection_id: u32, price: Balance, supply: u32) -> Self {
+            Self {
+                organizer: Self::env().caller(),
+                collection_id,
+                price,
+                available: supply,
+            }
+        }
+
+        /// Mints a ticket to the caller, who has to pay its price, and returns its id.
+        #[ink(message, payable)]
+        pub fn buy(&mut self) -> Result<u32> {
+            if self.env().transferred_value() != self.price {
+                return Err(Error::WrongPrice)
+            }
+            if self.available == 0 {
+                return Err(Error::SoldOut)
+            }
+            let buyer = self.env().caller();
+            let item_id = self.env().extension().mint_next(self.collection_id, buyer)?;
+            self.available -= 1;
+            self.env().emit_event(TicketSold { item_id, buyer });
+            Ok(item_id)
+        }
+
+        /// Checks in the ticket `holder` presents at the door, making it soulbound.
+        #[ink(message)]
+        pub fn check_in(&mut self, item_id: u32, holder: AccountId) -> Result<()> {
+            if self.env().caller() != self.organizer {
+                return Err(Error::NotOrganizer)
+            }
+            self.void(item_id, holder, CHECKED_IN)?;
+            self.env().emit_event(CheckedIn { item_id, holder });
+            Ok(())
+        }
+
+        /// Burns the caller's unused ticket, pays back its price and puts another ticket
+        /// up for sale.
+        #[ink(message)]
+        pub fn refund(&mut self, item_id: u32) -> Result<()> {
+            let holder = self.env().caller();
+            if self.env().extension().freeze_status(self.collection_id, item_id)?.0 {
+                return Err(Error::TicketVoid)
+            }
+            self.env()
+                .extension()
+                .burn(self.collection_id, item_id, holder)
+                .map_err(|error| match error {
+                    Psp02Error::OwnerMismatch => Error::NotTicketHolder,
+                    error => Error::Extension(error),
+                })?;
+            self.available += 1;
+            self.env().transfer(holder, self.price).map_err(|_| Error::RefundFailed)?;
+            self.env().emit_event(Refunded { item_id, holder });
+            Ok(())
+        }
+
+        /// Returns why a ticket is void, or `None` if it is still valid.
+        #[ink(message)]
+        pub fn void_reason(&self, item_id: u32) -> Result<Option<u32>> {
+            let (frozen, reason) =
+                self.env().extension().freeze_status(self.collection_id, item_id)?;
+            Ok(if frozen { reason } else { None })
+        }
+
+        /// Freezes the valid ticket `item_id` of `holder` with `reason`.
+        fn void(&mut self, item_id: u32, holder: AccountId, reason: u32) -> Result<()> {
+            let owner = self.env().extension().get_owner(self.collection_id, item_id)?;
+            if owner != Some(holder) {
+                return Err(Error::NotTicketHolder)
+            }
+            if self.env().extension().freeze_status(self.collection_id, item_id)?.0 {
+                return Err(Error::TicketVoid)
+            }
+            self.env().extension().freeze(self.collection_id, item_id, Some(reason))?;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::TicketingEnvironment as Env;
+        use ink::env::test;
+        use psp02_extension::replay::{
+            RecordedCall,
+            Replay,
+        };
+
+        const COLLECTION: u32 = 0;
+        const PRICE: Balance = 100;
+
+        fn accounts() -> test::DefaultAccounts<Env> {
+            test::default_accounts::<Env>()
+        }
+
+        /// Deploys the contract as the organizer, Alice, with `supply` tickets.
+        fn deploy(supply: u32) -> Ticketing {
+            test::set_caller::<Env>(accounts().alice);
+            Ticketing::new(COLLECTION, PRICE, supply)
+        }
+
+        fn buy_as(contract: &mut Ticketing, buyer: AccountId) -> Result<u32> {
+            test::set_caller::<Env>(buyer);
+            test::set_value_transferred::<Env>(PRICE);
+            contract.buy()
+        }
+
+        /// The calls voiding the valid ticket `item` of `holder` with `reason`.
+        fn void_calls(item: u32, holder: AccountId, reason: u32) -> Vec<RecordedCall> {
+            vec![
+                RecordedCall::new(0x162d, (COLLECTION, item), Some(holder)),
+                RecordedCall::new(0x4cb3, (COLLECTION, item), (false, None::<u32>)),
+                RecordedCall::new(0xce8e, (COLLECTION, item, Some(reason)), ()),
+            ]
+        }
+
+        #[ink::test]
+        fn checked_in_tickets_become_void() {
+            let bob = accounts().bob;
+            let mut calls = vec![RecordedCall::new(0xaeed, (COLLECTION, bob), 7u32)];
+            calls.extend(void_calls(7, bob, CHECKED_IN));
+            calls.extend([
+                RecordedCall::new(0x162d, (COLLECTION, 7u32), Some(bob)),
+                RecordedCall::new(0x4cb3, (COLLECTION, 7u32), (true, Some(CHECKED_IN))),
+                RecordedCall::new(0x4cb3, (COLLECTION, 7u32), (true, Some(CHECKED_IN))),
+                RecordedCall::new(0x4cb3, (COLLECTION, 7u32), (true, Some(CHECKED_IN))),
+            ]);
+            let replay = Replay::register(calls);
+            let mut contract = deploy(10);
+
+            assert_eq!(buy_as(&mut contract, bob).ok(), Some(7));
+            assert!(matches!(contract.check_in(7, bob), Err(Error::NotOrganizer)));
+            test::set_caller::<Env>(accounts().alice);
+            assert!(contract.check_in(7, bob).is_ok());
+            assert!(matches!(contract.check_in(7, bob), Err(Error::TicketVoid)));
+            assert_eq!(contract.void_reason(7).ok(), Some(Some(CHECKED_IN)));
+            // Checked-in tickets are not refunded.
+            test::set_caller::<Env>(bob);
+            assert!(matches!(contract.refund(7), Err(Error::TicketVoid)));
+            assert_eq!(test::recorded_events().count(), 2);
+            replay.assert_finished();
+        }
+
+        #[ink::test]
+        fn refunds_unused_tickets() {
+            let bob = accounts().bob;
+            let unfrozen = (false, None::<u32>);
+            let replay = Replay::register(vec![
+                RecordedCall::new(0xaeed, (COLLECTION, bob), 3u32),
+                RecordedCall::new(0x4cb3, (COLLECTION, 3u32), unfrozen),
+                RecordedCall::failed(0x6b71, (COLLECTION, 3u32, bob), 7),
+                RecordedCall::new(0x4cb3, (COLLECTION, 3u32), unfrozen),
+                RecordedCall::new(0x6b71, (COLLECTION, 3u32, bob), ()),
+            ]);
+            let mut contract = deploy(1);
+            test::set_account_balance::<Env>(test::callee::<Env>(), PRICE);
+            let balance = test::get_account_balance::<Env>(bob).unwrap();
+
+            assert_eq!(buy_as(&mut contract, bob).ok(), Some(3));
+            assert!(matches!(buy_as(&mut contract, bob), Err(Error::SoldOut)));
+            // Only the holder of a ticket gets its refund.
+            assert!(matches!(contract.refund(3), Err(Error::NotTicketHolder)));
+            assert!(contract.refund(3).is_ok());
+            assert_eq!(test::get_account_balance::<Env>(bob), Ok(balance + PRICE));
+            assert_eq!(contract.available, 1);
+            replay.assert_finished();
+        }
+
+        #[ink::test]
+        fn tickets_sell_for_their_price_only() {
+            let replay = Replay::register(vec![]);
+            let mut contract = deploy(10);
+
+            test::set_value_transferred::<Env>(PRICE - 1);
+            assert!(matches!(contract.buy(), Err(Error::WrongPrice)));
+            replay.assert_finished();
+        }
+    }
+}
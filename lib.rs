@@ -1,34 +1,354 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use ink::{
-    env::Environment
+    env::Environment,
+    prelude::vec::Vec,
 };
 
-type DefaultAccountId = <ink::env::DefaultEnvironment as Environment>::AccountId;
+mod bounded;
+#[cfg(feature = "std")]
+pub mod replay;
+
+pub use bounded::*;
+
+pub type DefaultAccountId = <ink::env::DefaultEnvironment as Environment>::AccountId;
 type DefaultBalance = <ink::env::DefaultEnvironment as Environment>::Balance;
+type DefaultBlockNumber = <ink::env::DefaultEnvironment as Environment>::BlockNumber;
+pub type DefaultHash = <ink::env::DefaultEnvironment as Environment>::Hash;
+
+/// The selector of the message a watcher contract is called with when an item of its
+/// collection is transferred outside the extension. The message takes the collection id,
+/// the item id, the previous owner and the new owner:
+/// `#[ink(message, selector = 0x445dcf12)]`.
+pub const ON_TRANSFER_SELECTOR: [u8; 4] = [0x44, 0x5d, 0xcf, 0x12];
+
+/// Returns the account the runtime calls the watcher of `collection_id` from. Watchers
+/// check that `self.env().caller()` is this account, as anybody else can call them with
+/// made up transfers.
+pub fn watcher_caller(collection_id: u32) -> DefaultAccountId {
+    // The runtime derives it from the pallet id `psp02/wt` like any pallet sub-account.
+    let mut account = [0u8; 32];
+    account[..4].copy_from_slice(b"modl");
+    account[4..12].copy_from_slice(b"psp02/wt");
+    account[12..16].copy_from_slice(&collection_id.to_le_bytes());
+    DefaultAccountId::from(account)
+}
+
+/// A statement by the runtime that `owner` owned an item at `block_number`.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct OwnershipAttestation {
+    pub block_number: DefaultBlockNumber,
+    pub collection_id: u32,
+    pub item_id: u32,
+    pub owner: DefaultAccountId,
+}
+
+/// A destructive operation on a collection that its confirmer has to approve.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AdminOp {
+    Destroy,
+    ForceTransfer { item_id: u32, dest: DefaultAccountId },
+    SetConfirmer(Option<DefaultAccountId>),
+}
 
+/// An admin operation waiting for its confirmation.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct PendingAdminOp {
+    pub op: AdminOp,
+    pub proposer: DefaultAccountId,
+    pub expires_at: DefaultBlockNumber,
+}
+
+/// A heavy operation on a collection, executed by the runtime across blocks.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DeferredOp {
+    /// Mints `count` items with unused ids to `owner`.
+    MintBatch { owner: DefaultAccountId, count: u32 },
+    Destroy,
+}
+
+/// A deferred operation waiting to be executed.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct QueuedOp {
+    /// The operation, reduced to the steps that are left.
+    pub op: DeferredOp,
+    pub collection_id: u32,
+    pub submitter: DefaultAccountId,
+}
+
+/// How far a batch of transfers got.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BatchProgress {
+    /// The number of transfers carried out, from the start of the batch.
+    pub completed: u32,
+    /// The status code of the transfer that stopped a best-effort batch, or `0` when it
+    /// ran out of transfers or gas.
+    pub status: u32,
+}
+
+impl BatchProgress {
+    /// Returns the error of the transfer that stopped a best-effort batch, if any.
+    pub fn stopped_by(&self) -> Option<Psp02Error> {
+        <Psp02Error as ink::env::chain_extension::FromStatusCode>::from_status_code(
+            self.status,
+        )
+        .err()
+    }
+}
+
+/// The last failed call of a contract to the extension, recorded by runtimes built with
+/// their `debug` feature.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LastError {
+    pub func_id: u16,
+    /// The SCALE encoded `DispatchError` of the runtime, to be decoded off-chain with the
+    /// runtime's metadata.
+    pub error: EncodedError,
+}
+
+/// Bytes taking up the rest of the input when decoded, as the length of a
+/// `DispatchError` depends on the runtime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct EncodedError(pub Vec<u8>);
+
+impl scale::Encode for EncodedError {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, dest: &mut O) {
+        dest.write(&self.0)
+    }
+}
+
+impl scale::Decode for EncodedError {
+    fn decode<I: scale::Input>(input: &mut I) -> core::result::Result<Self, scale::Error> {
+        let len = input.remaining_len()?.ok_or("the length of the error is unknown")?;
+        let mut bytes = ink::prelude::vec![0; len];
+        input.read(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// The extension as called by ink! contracts.
+///
+/// Queries are always answered in SCALE: the flat layout some queries offer is selected by
+/// a byte appended to their input, which these methods never append. It is meant for
+/// contracts built with other tooling, which call the function IDs directly.
 #[ink::chain_extension]
 pub trait Psp02Extension {
     type ErrorCode = Psp02Error;
 
     #[ink(extension = 0x162d)]
-    fn get_owner(asset_id: u32) -> Result<DefaultAccountId>;
+    fn get_owner(collection_id: u32, item_id: u32) -> Result<Option<DefaultAccountId>>;
 
     // PSP22 transfer
     #[ink(extension = 0xdb20)]
-    fn transfer(asset_id: u32, dest: DefaultAccountId, collection_id: u32)
+    fn transfer(collection_id: u32, item_id: u32, dest: DefaultAccountId)
+    -> Result<()>;
+
+    #[ink(extension = 0x3df4)]
+    fn transfer_batch(
+        collection_id: u32,
+        transfers: Vec<(u32, DefaultAccountId)>,
+        best_effort: bool,
+    ) -> Result<BatchProgress>;
+
+    // PSP34 approve
+    #[ink(extension = 0x1932)]
+    fn approve(collection_id: u32, item_id: u32, delegate: DefaultAccountId)
+    -> Result<()>;
+
+    #[ink(extension = 0x9c69)]
+    fn cancel_approval(collection_id: u32, item_id: u32, delegate: DefaultAccountId)
     -> Result<()>;
+
+    #[ink(extension = 0xb5e2)]
+    fn set_metadata(collection_id: u32, item_id: u32, data: Metadata)
+    -> Result<()>;
+
+    #[ink(extension = 0x3e09)]
+    fn clear_metadata(collection_id: u32, item_id: u32) -> Result<()>;
+
+    #[ink(extension = 0x817c)]
+    fn approvals_to(delegate: DefaultAccountId) -> Result<Vec<(u32, u32)>>;
+
+    #[ink(extension = 0x2612)]
+    fn revoke_all_approvals(delegate: DefaultAccountId) -> Result<Vec<(u32, u32)>>;
+
+    #[ink(extension = 0x145a)]
+    fn attest_ownership(collection_id: u32, item_id: u32)
+    -> Result<Option<(OwnershipAttestation, DefaultHash)>>;
+
+    #[ink(extension = 0xe8c5)]
+    fn verify_attestation(attestation: OwnershipAttestation) -> Result<bool>;
+
+    #[ink(extension = 0xce8e)]
+    fn freeze(collection_id: u32, item_id: u32, reason: Option<u32>) -> Result<()>;
+
+    #[ink(extension = 0xa49a)]
+    fn thaw(collection_id: u32, item_id: u32) -> Result<()>;
+
+    #[ink(extension = 0x4cb3)]
+    fn freeze_status(collection_id: u32, item_id: u32) -> Result<(bool, Option<u32>)>;
+
+    #[ink(extension = 0x0d2d)]
+    fn act_for(real: Option<DefaultAccountId>) -> Result<()>;
+
+    #[ink(extension = 0xb65e)]
+    fn acting_for() -> Result<Option<DefaultAccountId>>;
+
+    #[ink(extension = 0x5873)]
+    fn admin_op(collection_id: u32, op: AdminOp) -> Result<bool>;
+
+    #[ink(extension = 0xe63e)]
+    fn confirm_admin_op(collection_id: u32) -> Result<()>;
+
+    #[ink(extension = 0xd123)]
+    fn cancel_admin_op(collection_id: u32) -> Result<()>;
+
+    #[ink(extension = 0xc29f)]
+    fn pending_admin_op(collection_id: u32) -> Result<Option<PendingAdminOp>>;
+
+    #[ink(extension = 0x16fb)]
+    fn admin_confirmer(collection_id: u32) -> Result<Option<DefaultAccountId>>;
+
+    #[ink(extension = 0x32ae)]
+    fn next_free_item_id(collection_id: u32) -> Result<Option<u32>>;
+
+    #[ink(extension = 0xaeed)]
+    fn mint_next(collection_id: u32, owner: DefaultAccountId) -> Result<u32>;
+
+    #[ink(extension = 0xbb93)]
+    fn collections_owned(account: DefaultAccountId, start_after: Option<u32>, limit: u32)
+    -> Result<Vec<u32>>;
+
+    #[ink(extension = 0x253d)]
+    fn grant_capability(grantee: DefaultAccountId, collection_id: u32, func_ids: Vec<u16>)
+    -> Result<()>;
+
+    #[ink(extension = 0xe253)]
+    fn capabilities(grantor: DefaultAccountId, grantee: DefaultAccountId)
+    -> Result<Vec<(u32, u16)>>;
+
+    #[ink(extension = 0xeaf3)]
+    fn verify_snapshot_membership(
+        collection_id: u32,
+        block_number: DefaultBlockNumber,
+        account: DefaultAccountId,
+        proof: Vec<DefaultHash>,
+    ) -> Result<bool>;
+
+    #[ink(extension = 0xc60a)]
+    fn set_memo(collection_id: u32, item_id: u32, memo: Memo) -> Result<()>;
+
+    #[ink(extension = 0x05a6)]
+    fn memo(collection_id: u32, item_id: u32) -> Result<Option<Vec<u8>>>;
+
+    #[ink(extension = 0x813e)]
+    fn set_transfer_cooldown(collection_id: u32, cooldown: Option<DefaultBlockNumber>)
+    -> Result<()>;
+
+    #[ink(extension = 0x1e27)]
+    fn transfer_cooldown(collection_id: u32) -> Result<Option<DefaultBlockNumber>>;
+
+    #[ink(extension = 0xe94b)]
+    fn free_holding(collection_id: u32) -> Result<Option<bool>>;
+
+    #[ink(extension = 0x267a)]
+    fn submit_deferred_op(collection_id: u32, op: DeferredOp) -> Result<u32>;
+
+    #[ink(extension = 0x47b5)]
+    fn deferred_op_status(op_id: u32) -> Result<Option<QueuedOp>>;
+
+    #[ink(extension = 0x9c83)]
+    fn cancel_deferred_op(op_id: u32) -> Result<()>;
+
+    #[ink(extension = 0x5723)]
+    fn set_watcher(collection_id: u32, watcher: Option<DefaultAccountId>) -> Result<()>;
+
+    #[ink(extension = 0x47e5)]
+    fn watcher(collection_id: u32) -> Result<Option<DefaultAccountId>>;
+
+    #[ink(extension = 0x6b71)]
+    fn burn(collection_id: u32, item_id: u32, expected_owner: DefaultAccountId) -> Result<()>;
+
+    // PSP34 allowance
+    #[ink(extension = 0x4790)]
+    fn allowance(
+        collection_id: u32,
+        owner: DefaultAccountId,
+        operator: DefaultAccountId,
+        item_id: Option<u32>,
+    ) -> Result<bool>;
+
+    #[ink(extension = 0x615c)]
+    fn mint_next_with_provenance(
+        collection_id: u32,
+        owner: DefaultAccountId,
+        hash: DefaultHash,
+    ) -> Result<u32>;
+
+    #[ink(extension = 0x27cd)]
+    fn verify_provenance(collection_id: u32, item_id: u32, hash: DefaultHash) -> Result<bool>;
+
+    #[ink(extension = 0xdfc4)]
+    fn collection_nonce(collection_id: u32) -> Result<u64>;
+
+    #[ink(extension = 0x1ae3)]
+    fn last_error() -> Result<Option<LastError>>;
 }
 
 #[derive(scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum Psp02Error {
     TotalSupplyFailed,
+    /// The destination of a transfer was the all-zero account.
+    TransferToZeroAccount,
+    /// The destination of a transfer already owns the item.
+    TransferToOwner,
+    /// The destination of a transfer was the calling contract, which the runtime forbids.
+    TransferToSelf,
+    /// The call failed and was reverted. Only returned by runtimes built with their
+    /// `debug` feature; `last_error` returns why.
+    CallFailed,
+    /// The item was transferred too recently for the cooldown of its collection.
+    TransferCooldown,
+    /// The output of the runtime could not be decoded. Only returned with the
+    /// `defensive` feature; the contract panics otherwise.
+    DecodeFailed,
+    /// The runtime returned a status code this contract does not know. Only returned
+    /// with the `defensive` feature; the contract panics otherwise.
+    UnknownStatusCode(u32),
+    /// The item to burn was not owned by the expected owner.
+    OwnerMismatch,
+    /// `sync` of the reference contract was asked to continue after another collection
+    /// than the one it returned last.
+    SyncOutOfOrder,
+    /// The collection was destroyed, or is queued for destruction.
+    CollectionDestroyed,
+    /// The NFT pallet refused a transfer of a best-effort batch, e.g. because the item is
+    /// frozen.
+    TransferFailed,
 }
 
 pub type Result<T> = core::result::Result<T, Psp02Error>;
 
 impl From<scale::Error> for Psp02Error {
+    #[cfg(feature = "defensive")]
+    fn from(_: scale::Error) -> Self {
+        Self::DecodeFailed
+    }
+
+    #[cfg(not(feature = "defensive"))]
     fn from(_: scale::Error) -> Self {
         panic!("encountered unexpected invalid SCALE encoding")
     }
@@ -36,9 +356,21 @@ impl From<scale::Error> for Psp02Error {
 
 impl ink::env::chain_extension::FromStatusCode for Psp02Error {
     fn from_status_code(status_code: u32) -> core::result::Result<(), Self> {
+        // The registry of the runtime, `runtime/src/status.rs`, freezes these numbers.
         match status_code {
             0 => Ok(()),
             1 => Err(Self::TotalSupplyFailed),
+            2 => Err(Self::TransferToZeroAccount),
+            3 => Err(Self::TransferToOwner),
+            4 => Err(Self::TransferToSelf),
+            5 => Err(Self::CallFailed),
+            6 => Err(Self::TransferCooldown),
+            7 => Err(Self::OwnerMismatch),
+            8 => Err(Self::CollectionDestroyed),
+            9 => Err(Self::TransferFailed),
+            #[cfg(feature = "defensive")]
+            _ => Err(Self::UnknownStatusCode(status_code)),
+            #[cfg(not(feature = "defensive"))]
             _ => panic!("encountered unknown status code"),
         }
     }
@@ -62,44 +394,797 @@ impl Environment for CustomEnvironment {
     type ChainExtension = crate::Psp02Extension;
 }
 
-#[ink::contract(env = crate::CustomEnvironment)]
-mod psp02_ext {
-    use crate::DefaultAccountId;
+/// Defines the reference contract in a module named `psp02_ext`, selecting its messages
+/// by `namespace`, signing its events with the name of `contract` and running it in
+/// `env`.
+///
+/// Messages are selected by the first four bytes of BLAKE2 of `"<namespace>::<message>"`
+/// and events signed with `"<contract>::<event>"`, so a chain shipping a customized fork
+/// of the contract keeps it apart from the canonical one in multi-chain tooling by
+/// passing its own namespace and name. The function IDs of the extension do not change.
+/// ink! ties the events of a contract to the type of its environment, so a crate
+/// defining the contract next to this one's passes an environment of its own rather
+/// than [`CustomEnvironment`]:
+///
+/// ```
+/// use ink::env::{
+///     DefaultEnvironment,
+///     Environment,
+/// };
+///
+/// pub enum AcmeEnvironment {}
+///
+/// impl Environment for AcmeEnvironment {
+///     const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+///
+///     type AccountId = <DefaultEnvironment as Environment>::AccountId;
+///     type Balance = <DefaultEnvironment as Environment>::Balance;
+///     type Hash = <DefaultEnvironment as Environment>::Hash;
+///     type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+///     type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+///
+///     type ChainExtension = psp02_extension::Psp02Extension;
+/// }
+///
+/// psp02_extension::psp02_contract! {
+///     namespace: "AcmeNft",
+///     contract: AcmeNft,
+///     env: crate::AcmeEnvironment
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! psp02_contract {
+    (namespace: $namespace:tt, contract: $contract:ident, env: $env:path) => {
+        #[ink::contract(env = $env)]
+        mod psp02_ext {
+            use $crate::{
+                AdminOp,
+                BatchProgress,
+                DefaultAccountId,
+                DefaultHash,
+                DeferredOp,
+                LastError,
+                Memo,
+                Metadata,
+                OwnershipAttestation,
+                PendingAdminOp,
+                QueuedOp,
+            };
 
-    use super::{
-        Result
-    };
+            use ink::prelude::vec::Vec;
 
-    /// A chain extension which implements the PSP-22 fungible token standard.
-    /// For more details see <https://github.com/w3f/PSPs/blob/master/PSPs/psp-22.md>
-    #[ink(storage)]
-    #[derive(Default)]
-    pub struct Psp02Extension {}
-
-    impl Psp02Extension {
-        /// Creates a new instance of this contract.
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            Default::default()
-        }
+            use $crate::Result;
 
-        // PSP22 Metadata interfaces
+            /// Emitted whenever an item is transferred through this contract.
+            #[ink(event)]
+            pub struct Transfer {
+                #[ink(topic)]
+                collection_id: u32,
+                #[ink(topic)]
+                item_id: u32,
+                from: DefaultAccountId,
+                #[ink(topic)]
+                to: DefaultAccountId,
+            }
 
-        /// Returns the token name of the specified asset.
-        #[ink(message, selector = 0x3d261bd4)]
-        pub fn get_owner(&self, asset_id: u32) -> Result<DefaultAccountId> {
-            self.env().extension().get_owner(asset_id)
-        }
-        // PSP22 transfer
-
-        /// Transfers `value` amount of specified asset from the caller's account to the
-        /// account `to`.
-        #[ink(message, selector = 0xdb20f9f5)]
-        pub fn transfer_nft(
-            &mut self,
-            asset_id: u32, dest: DefaultAccountId, collection_id: u32
-        ) -> Result<()> {
-            self.env().extension().transfer(asset_id, dest, collection_id)
+            /// Emitted whenever a delegate is approved or its approval is cancelled.
+            #[ink(event)]
+            pub struct Approval {
+                #[ink(topic)]
+                collection_id: u32,
+                #[ink(topic)]
+                item_id: u32,
+                owner: DefaultAccountId,
+                #[ink(topic)]
+                delegate: DefaultAccountId,
+                approved: bool,
+            }
+
+            /// Emitted whenever `sync` finds that this contract started or stopped owning a
+            /// collection.
+            #[ink(event)]
+            pub struct Administered {
+                #[ink(topic)]
+                collection_id: u32,
+                administered: bool,
+            }
+
+            /// A chain extension which implements the PSP-22 fungible token standard.
+            /// For more details see <https://github.com/w3f/PSPs/blob/master/PSPs/psp-22.md>
+            #[ink(storage)]
+            #[derive(Default)]
+            pub struct $contract {
+                /// The collections this contract owned as of the last completed `sync`.
+                collections: Vec<u32>,
+                /// The collections found so far by the `sync` in progress.
+                syncing: Vec<u32>,
+                /// The collection the `sync` in progress continues after.
+                sync_cursor: Option<u32>,
+            }
+
+            #[ink(namespace = $namespace)]
+            impl $contract {
+                /// Creates a new instance of this contract.
+                #[ink(constructor)]
+                pub fn new() -> Self {
+                    Default::default()
+                }
+
+                /// Transfers items of a collection to their destinations in order and returns how
+                /// many were transferred.
+                ///
+                /// With `best_effort`, the runtime stops once the gas left does not cover the next
+                /// transfer, or at the first transfer it rejects, instead of failing, and reports
+                /// the status of that transfer, so the remaining transfers can be sent again.
+                #[ink(message)]
+                pub fn transfer_batch(
+                    &mut self,
+                    collection_id: u32, transfers: Vec<(u32, DefaultAccountId)>, best_effort: bool
+                ) -> Result<BatchProgress> {
+                    let progress = self.env().extension().transfer_batch(
+                        collection_id,
+                        transfers.clone(),
+                        best_effort,
+                    )?;
+                    let completed = progress.completed as usize;
+                    let from = self.operating_as()?;
+                    for (item_id, to) in transfers.into_iter().take(completed) {
+                        self.env().emit_event(Transfer {
+                            collection_id,
+                            item_id,
+                            from,
+                            to,
+                        });
+                    }
+                    Ok(progress)
+                }
+
+                /// Cancels the approval of `delegate` for the item.
+                #[ink(message)]
+                pub fn cancel_approval(
+                    &mut self,
+                    collection_id: u32, item_id: u32, delegate: DefaultAccountId
+                ) -> Result<()> {
+                    self.env().extension().cancel_approval(collection_id, item_id, delegate)?;
+                    self.env().emit_event(Approval {
+                        collection_id,
+                        item_id,
+                        owner: self.operating_as()?,
+                        delegate,
+                        approved: false,
+                    });
+                    Ok(())
+                }
+
+                /// Sets the metadata of an item of a collection administered by this contract.
+                #[ink(message)]
+                pub fn set_metadata(
+                    &mut self,
+                    collection_id: u32, item_id: u32, data: Metadata
+                ) -> Result<()> {
+                    self.env().extension().set_metadata(collection_id, item_id, data)
+                }
+
+                /// Clears the metadata of an item of a collection administered by this contract.
+                #[ink(message)]
+                pub fn clear_metadata(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+                    self.env().extension().clear_metadata(collection_id, item_id)
+                }
+
+                /// Returns the `(collection_id, item_id)` pairs this contract approved `delegate`
+                /// to transfer.
+                #[ink(message)]
+                pub fn approvals_to(&self, delegate: DefaultAccountId) -> Result<Vec<(u32, u32)>> {
+                    self.env().extension().approvals_to(delegate)
+                }
+
+                /// Cancels every approval this contract granted to `delegate`.
+                #[ink(message)]
+                pub fn revoke_all_approvals(
+                    &mut self,
+                    delegate: DefaultAccountId
+                ) -> Result<Vec<(u32, u32)>> {
+                    let revoked = self.env().extension().revoke_all_approvals(delegate)?;
+                    let owner = self.operating_as()?;
+                    for &(collection_id, item_id) in &revoked {
+                        self.env().emit_event(Approval {
+                            collection_id,
+                            item_id,
+                            owner,
+                            delegate,
+                            approved: false,
+                        });
+                    }
+                    Ok(revoked)
+                }
+
+                /// Has the runtime attest the current owner of the item.
+                ///
+                /// Returns the attestation along with the hash the runtime committed to, or `None`
+                /// if the item does not exist.
+                #[ink(message)]
+                pub fn attest_ownership(
+                    &mut self,
+                    collection_id: u32, item_id: u32
+                ) -> Result<Option<(OwnershipAttestation, DefaultHash)>> {
+                    self.env().extension().attest_ownership(collection_id, item_id)
+                }
+
+                /// Returns whether `attestation` was issued by the runtime.
+                #[ink(message)]
+                pub fn verify_attestation(
+                    &self,
+                    attestation: OwnershipAttestation
+                ) -> Result<bool> {
+                    self.env().extension().verify_attestation(attestation)
+                }
+
+                /// Freezes an item of a collection this contract is the freezer of, recording
+                /// an optional `reason` code.
+                #[ink(message)]
+                pub fn freeze(
+                    &mut self,
+                    collection_id: u32, item_id: u32, reason: Option<u32>
+                ) -> Result<()> {
+                    self.env().extension().freeze(collection_id, item_id, reason)
+                }
+
+                /// Thaws an item of a collection this contract is the freezer of.
+                #[ink(message)]
+                pub fn thaw(&mut self, collection_id: u32, item_id: u32) -> Result<()> {
+                    self.env().extension().thaw(collection_id, item_id)
+                }
+
+                /// Returns whether the item is frozen and the reason code it was frozen with.
+                #[ink(message)]
+                pub fn freeze_status(
+                    &self,
+                    collection_id: u32, item_id: u32
+                ) -> Result<(bool, Option<u32>)> {
+                    self.env().extension().freeze_status(collection_id, item_id)
+                }
+
+                /// Makes the following calls operate on the items of `real`, who must have made
+                /// this contract their proxy, or on this contract's own items if `None`.
+                #[ink(message)]
+                pub fn act_for(&mut self, real: Option<DefaultAccountId>) -> Result<()> {
+                    self.env().extension().act_for(real)
+                }
+
+                /// Returns the user this contract currently acts for, if any.
+                #[ink(message)]
+                pub fn acting_for(&self) -> Result<Option<DefaultAccountId>> {
+                    self.env().extension().acting_for()
+                }
+
+                /// Returns the account the calls of this contract operate as: the user it acts
+                /// for, or the contract itself. Once a call succeeded, the runtime let it
+                /// operate as this account.
+                fn operating_as(&self) -> Result<DefaultAccountId> {
+                    Ok(self.acting_for()?.unwrap_or_else(|| self.env().account_id()))
+                }
+
+                /// Proposes an admin operation on a collection owned by this contract.
+                ///
+                /// Returns `true` if the operation was executed right away because the collection
+                /// has no confirmer, `false` if it now waits for the confirmer.
+                #[ink(message)]
+                pub fn admin_op(&mut self, collection_id: u32, op: AdminOp) -> Result<bool> {
+                    self.env().extension().admin_op(collection_id, op)
+                }
+
+                /// Confirms the admin operation pending on a collection this contract confirms.
+                #[ink(message)]
+                pub fn confirm_admin_op(&mut self, collection_id: u32) -> Result<()> {
+                    self.env().extension().confirm_admin_op(collection_id)
+                }
+
+                /// Drops the admin operation pending on the collection.
+                #[ink(message)]
+                pub fn cancel_admin_op(&mut self, collection_id: u32) -> Result<()> {
+                    self.env().extension().cancel_admin_op(collection_id)
+                }
+
+                /// Returns the admin operation pending on the collection, if any.
+                #[ink(message)]
+                pub fn pending_admin_op(
+                    &self,
+                    collection_id: u32
+                ) -> Result<Option<PendingAdminOp>> {
+                    self.env().extension().pending_admin_op(collection_id)
+                }
+
+                /// Returns the account confirming admin operations on the collection, if any.
+                #[ink(message)]
+                pub fn admin_confirmer(
+                    &self,
+                    collection_id: u32
+                ) -> Result<Option<DefaultAccountId>> {
+                    self.env().extension().admin_confirmer(collection_id)
+                }
+
+                /// Returns an item id of the collection that is not taken yet, or `None` if the
+                /// runtime gave up looking for one; asking again continues the search.
+                ///
+                /// Use this instead of a counter in contract storage, which drifts as soon as
+                /// items get minted through extrinsics.
+                #[ink(message)]
+                pub fn next_free_item_id(&mut self, collection_id: u32) -> Result<Option<u32>> {
+                    self.env().extension().next_free_item_id(collection_id)
+                }
+
+                /// Mints an item of a collection this contract is the issuer of to `owner`, with
+                /// the id picked by the runtime.
+                ///
+                /// Returns the id of the minted item.
+                #[ink(message)]
+                pub fn mint_next(
+                    &mut self,
+                    collection_id: u32, owner: DefaultAccountId
+                ) -> Result<u32> {
+                    self.env().extension().mint_next(collection_id, owner)
+                }
+
+                /// Mints an item like `mint_next` and anchors `hash`, e.g. of the original
+                /// artwork, to it. Unlike metadata, the hash can never be changed.
+                ///
+                /// Returns the id of the minted item.
+                #[ink(message)]
+                pub fn mint_next_with_provenance(
+                    &mut self,
+                    collection_id: u32,
+                    owner: DefaultAccountId,
+                    hash: DefaultHash,
+                ) -> Result<u32> {
+                    self.env().extension().mint_next_with_provenance(collection_id, owner, hash)
+                }
+
+                /// Returns whether `hash` is the content hash anchored to an existing item.
+                #[ink(message)]
+                pub fn verify_provenance(
+                    &self,
+                    collection_id: u32,
+                    item_id: u32,
+                    hash: DefaultHash,
+                ) -> Result<bool> {
+                    self.env().extension().verify_provenance(collection_id, item_id, hash)
+                }
+
+                /// Returns the number of calls of the contract through the extension that
+                /// changed `collection_id`.
+                ///
+                /// Orders signed off-chain can carry the nonce they were made at, and are only
+                /// settled if it did not change in the meantime.
+                #[ink(message)]
+                pub fn collection_nonce(&self, collection_id: u32) -> Result<u64> {
+                    self.env().extension().collection_nonce(collection_id)
+                }
+
+                /// Reconciles the collections this contract tracks with the ones it owns, which
+                /// change outside of this contract when collections are created, transferred or
+                /// destroyed through extrinsics.
+                ///
+                /// A sync pages through `collections_owned`, `page` collections per call. It
+                /// starts over if `collection` is `None` and otherwise continues after
+                /// `collection`, which has to be the one the previous call returned. Once a page
+                /// comes back empty, the tracked collections are replaced by the ones found, and
+                /// `Administered` is emitted for each collection that was added or dropped.
+                ///
+                /// Returns the collection to continue after, or `None` once the sync completed.
+                #[ink(message)]
+                pub fn sync(&mut self, collection: Option<u32>, page: u32) -> Result<Option<u32>> {
+                    match collection {
+                        None => self.syncing.clear(),
+                        Some(_) if collection != self.sync_cursor => {
+                            return Err($crate::Psp02Error::SyncOutOfOrder)
+                        }
+                        Some(_) => {}
+                    }
+                    let account = self.env().account_id();
+                    let found =
+                        self.env().extension().collections_owned(account, collection, page)?;
+                    self.sync_cursor = found.last().copied();
+                    if self.sync_cursor.is_some() {
+                        self.syncing.extend(found);
+                        return Ok(self.sync_cursor)
+                    }
+
+                    let found = core::mem::take(&mut self.syncing);
+                    for &collection_id in &self.collections {
+                        if !found.contains(&collection_id) {
+                            self.env().emit_event(Administered {
+                                collection_id,
+                                administered: false,
+                            });
+                        }
+                    }
+                    for &collection_id in &found {
+                        if !self.collections.contains(&collection_id) {
+                            self.env().emit_event(Administered {
+                                collection_id,
+                                administered: true,
+                            });
+                        }
+                    }
+                    self.collections = found;
+                    Ok(None)
+                }
+
+                /// Returns the collections this contract owned as of the last completed `sync`.
+                #[ink(message)]
+                pub fn tracked_collections(&self) -> Vec<u32> {
+                    self.collections.clone()
+                }
+
+                /// Returns up to `limit` collections owned by `account`, following `start_after`.
+                ///
+                /// Collections are not sorted by id; fetch the next page by passing the last
+                /// collection of the current one as `start_after`. The runtime caps `limit`.
+                #[ink(message)]
+                pub fn collections_owned(
+                    &self,
+                    account: DefaultAccountId, start_after: Option<u32>, limit: u32
+                ) -> Result<Vec<u32>> {
+                    self.env().extension().collections_owned(account, start_after, limit)
+                }
+
+                /// Lets `grantee` call the extension functions `func_ids` on the collection as
+                /// this contract, once it calls `act_for` with this contract. Replaces what was
+                /// granted on the collection before; an empty `func_ids` withdraws it.
+                #[ink(message)]
+                pub fn grant_capability(
+                    &mut self,
+                    grantee: DefaultAccountId, collection_id: u32, func_ids: Vec<u16>
+                ) -> Result<()> {
+                    self.env().extension().grant_capability(grantee, collection_id, func_ids)
+                }
+
+                /// Returns the `(collection_id, func_id)` pairs `grantor` lets `grantee` call.
+                #[ink(message)]
+                pub fn capabilities(
+                    &self,
+                    grantor: DefaultAccountId, grantee: DefaultAccountId
+                ) -> Result<Vec<(u32, u16)>> {
+                    self.env().extension().capabilities(grantor, grantee)
+                }
+
+                /// Returns whether `proof` shows that `account` held items of a collection at
+                /// `block_number`, according to the snapshot published by governance.
+                #[ink(message)]
+                pub fn verify_snapshot_membership(
+                    &self,
+                    collection_id: u32, block_number: BlockNumber,
+                    account: DefaultAccountId, proof: Vec<DefaultHash>
+                ) -> Result<bool> {
+                    self.env()
+                        .extension()
+                        .verify_snapshot_membership(collection_id, block_number, account, proof)
+                }
+
+                /// Sets the memo of an item owned by this contract, or removes it if `memo` is
+                /// empty.
+                #[ink(message)]
+                pub fn set_memo(
+                    &mut self,
+                    collection_id: u32, item_id: u32, memo: Memo
+                ) -> Result<()> {
+                    self.env().extension().set_memo(collection_id, item_id, memo)
+                }
+
+                /// Returns the memo of an item, if its current owner wrote one.
+                #[ink(message)]
+                pub fn memo(&self, collection_id: u32, item_id: u32) -> Result<Option<Vec<u8>>> {
+                    self.env().extension().memo(collection_id, item_id)
+                }
+
+                /// Sets the minimum number of blocks between transfers of an item of a collection
+                /// owned by this contract, or removes it.
+                #[ink(message)]
+                pub fn set_transfer_cooldown(
+                    &mut self,
+                    collection_id: u32, cooldown: Option<BlockNumber>
+                ) -> Result<()> {
+                    self.env().extension().set_transfer_cooldown(collection_id, cooldown)
+                }
+
+                /// Returns the transfer cooldown of a collection, if it has one.
+                #[ink(message)]
+                pub fn transfer_cooldown(&self, collection_id: u32) -> Result<Option<BlockNumber>> {
+                    self.env().extension().transfer_cooldown(collection_id)
+                }
+
+                /// Returns whether a collection was force-created with free holding, in which case
+                /// minting its items reserves no deposit from the minter, if it exists.
+                #[ink(message)]
+                pub fn free_holding(&self, collection_id: u32) -> Result<Option<bool>> {
+                    self.env().extension().free_holding(collection_id)
+                }
+
+                /// Queues an operation too heavy for a single call, to be executed by the runtime
+                /// across blocks, and returns its id.
+                #[ink(message)]
+                pub fn submit_deferred_op(
+                    &mut self,
+                    collection_id: u32, op: DeferredOp
+                ) -> Result<u32> {
+                    self.env().extension().submit_deferred_op(collection_id, op)
+                }
+
+                /// Returns what is left of a deferred operation, or `None` once it is done, failed
+                /// or was cancelled.
+                #[ink(message)]
+                pub fn deferred_op_status(&self, op_id: u32) -> Result<Option<QueuedOp>> {
+                    self.env().extension().deferred_op_status(op_id)
+                }
+
+                /// Cancels the remaining steps of a deferred operation this contract submitted.
+                #[ink(message)]
+                pub fn cancel_deferred_op(&mut self, op_id: u32) -> Result<()> {
+                    self.env().extension().cancel_deferred_op(op_id)
+                }
+
+                /// Registers a contract to be called with `ON_TRANSFER_SELECTOR` whenever an
+                /// item of a collection is transferred outside the extension, or stops calling
+                /// it if `watcher` is `None`. Only the collection owner can set the watcher.
+                #[ink(message)]
+                pub fn set_watcher(
+                    &mut self,
+                    collection_id: u32,
+                    watcher: Option<AccountId>,
+                ) -> Result<()> {
+                    self.env().extension().set_watcher(collection_id, watcher)
+                }
+
+                /// Returns the contract notified of the transfers of a collection, if any.
+                #[ink(message)]
+                pub fn watcher(&self, collection_id: u32) -> Result<Option<AccountId>> {
+                    self.env().extension().watcher(collection_id)
+                }
+
+                /// Burns an item, provided it is still owned by `expected_owner`. Fails with
+                /// `OwnerMismatch` if the item changed hands since its owner was looked up.
+                #[ink(message)]
+                pub fn burn(
+                    &mut self,
+                    collection_id: u32,
+                    item_id: u32,
+                    expected_owner: AccountId,
+                ) -> Result<()> {
+                    self.env().extension().burn(collection_id, item_id, expected_owner)
+                }
+
+                /// Returns the last failed call of this contract to the extension, if the runtime
+                /// was built with its `debug` feature and no block started since.
+                #[ink(message)]
+                pub fn last_error(&self) -> Result<Option<LastError>> {
+                    self.env().extension().last_error()
+                }
+            }
+
+            // The messages of PSP22 and PSP34 keep the selectors of those standards, so they are
+            // not namespaced.
+            impl $contract {
+                /// Returns the owner of the item, if it exists.
+                #[ink(message, selector = 0x3d261bd4)]
+                pub fn get_owner(
+                    &self,
+                    collection_id: u32, item_id: u32
+                ) -> Result<Option<DefaultAccountId>> {
+                    self.env().extension().get_owner(collection_id, item_id)
+                }
+
+                /// Transfers `value` amount of specified asset from the caller's account to the
+                /// account `to`.
+                #[ink(message, selector = 0xdb20f9f5)]
+                pub fn transfer_nft(
+                    &mut self,
+                    asset_id: u32, dest: DefaultAccountId, collection_id: u32
+                ) -> Result<()> {
+                    self.env().extension().transfer(collection_id, asset_id, dest)?;
+                    self.env().emit_event(Transfer {
+                        collection_id,
+                        item_id: asset_id,
+                        from: self.operating_as()?,
+                        to: dest,
+                    });
+                    Ok(())
+                }
+
+                /// Approves `delegate` to transfer the item owned by this contract.
+                ///
+                /// Depending on the runtime's NFT pallet this either replaces the current
+                /// delegate or adds another one.
+                #[ink(message, selector = 0x1932a8b0)]
+                pub fn approve(
+                    &mut self,
+                    collection_id: u32, item_id: u32, delegate: DefaultAccountId
+                ) -> Result<()> {
+                    self.env().extension().approve(collection_id, item_id, delegate)?;
+                    self.env().emit_event(Approval {
+                        collection_id,
+                        item_id,
+                        owner: self.operating_as()?,
+                        delegate,
+                        approved: true,
+                    });
+                    Ok(())
+                }
+
+                /// Returns whether `operator` may transfer an item of `owner`, or every item of
+                /// `owner` in the collection if `item_id` is `None`, as `allowance` of PSP34 does.
+                /// The whole collection is approved to proxies of `owner` and contracts `owner`
+                /// granted the capability to call `transfer` on it.
+                #[ink(message, selector = 0x4790f55a)]
+                pub fn allowance(
+                    &self,
+                    collection_id: u32,
+                    owner: AccountId,
+                    operator: AccountId,
+                    item_id: Option<u32>,
+                ) -> Result<bool> {
+                    self.env().extension().allowance(collection_id, owner, operator, item_id)
+                }
+            }
         }
+    };
+}
+
+psp02_contract! {
+    namespace: "Psp02",
+    contract: Psp02Extension,
+    env: crate::CustomEnvironment
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        psp02_ext::Psp02Extension,
+        replay::{
+            RecordedCall,
+            Replay,
+        },
+        CustomEnvironment,
+        Psp02Error,
+    };
+    use ink::{
+        env::test,
+        reflect::DispatchableMessageInfo,
+    };
+
+    /// Fails to compile unless the reference contract has a message selected by `selector`,
+    /// and fails unless it is the message named `label`.
+    macro_rules! assert_selector {
+        ($selector:literal, $label:literal) => {
+            assert_eq!(<Psp02Extension as DispatchableMessageInfo<$selector>>::LABEL, $label)
+        };
+    }
+
+    #[test]
+    fn canonical_selectors_are_frozen() {
+        // Deployed callers encode these selectors. Extend this list, never change it.
+        assert_selector!(0x3d261bd4, "get_owner");
+        assert_selector!(0xdb20f9f5, "transfer_nft");
+        assert_selector!(0x3df435df, "transfer_batch");
+        assert_selector!(0x1932a8b0, "approve");
+        assert_selector!(0x9c69f01f, "cancel_approval");
+        assert_selector!(0xb5e283b2, "set_metadata");
+        assert_selector!(0x3e09eaaf, "clear_metadata");
+        assert_selector!(0x817cf508, "approvals_to");
+        assert_selector!(0x261230be, "revoke_all_approvals");
+        assert_selector!(0x145a48e3, "attest_ownership");
+        assert_selector!(0xe8c5ba74, "verify_attestation");
+        assert_selector!(0xce8e6079, "freeze");
+        assert_selector!(0xa49ad11b, "thaw");
+        assert_selector!(0x4cb3461f, "freeze_status");
+        assert_selector!(0x0d2d93da, "act_for");
+        assert_selector!(0xb65e3d1c, "acting_for");
+        assert_selector!(0x5873c5e0, "admin_op");
+        assert_selector!(0xe63ee854, "confirm_admin_op");
+        assert_selector!(0xd123a204, "cancel_admin_op");
+        assert_selector!(0xc29fc748, "pending_admin_op");
+        assert_selector!(0x16fba680, "admin_confirmer");
+        assert_selector!(0x32ae9f1b, "next_free_item_id");
+        assert_selector!(0xaeed9c5d, "mint_next");
+        assert_selector!(0xbb935075, "collections_owned");
+        assert_selector!(0x253d37f3, "grant_capability");
+        assert_selector!(0xe2537353, "capabilities");
+        assert_selector!(0xeaf3a8c5, "verify_snapshot_membership");
+        assert_selector!(0xc60a1c06, "set_memo");
+        assert_selector!(0x05a67005, "memo");
+        assert_selector!(0x813e0806, "set_transfer_cooldown");
+        assert_selector!(0x1e27675b, "transfer_cooldown");
+        assert_selector!(0xe94b6ebf, "free_holding");
+        assert_selector!(0x267a0ad7, "submit_deferred_op");
+        assert_selector!(0x47b5b111, "deferred_op_status");
+        assert_selector!(0x9c83981f, "cancel_deferred_op");
+        assert_selector!(0x5723b3b0, "set_watcher");
+        assert_selector!(0x47e5af43, "watcher");
+        assert_selector!(0x6b718f78, "burn");
+        assert_selector!(0x4790f55a, "allowance");
+        assert_selector!(0x615cbb24, "mint_next_with_provenance");
+        assert_selector!(0x27cd2cb9, "verify_provenance");
+        assert_selector!(0xdfc4a86a, "collection_nonce");
+        assert_selector!(0x8d36bc0c, "sync");
+        assert_selector!(0xffbb0f5d, "tracked_collections");
+        assert_selector!(0x1ae3d4f4, "last_error");
+    }
+
+    #[test]
+    fn watcher_caller_is_the_runtime_sub_account() {
+        let mut expected = [0u8; 32];
+        expected[..16].copy_from_slice(b"modlpsp02/wt\x07\0\0\0");
+        assert_eq!(crate::watcher_caller(7), crate::DefaultAccountId::from(expected));
+    }
+
+    /// The call `sync` makes for the page of `page` collections after `start_after`.
+    fn page(start_after: Option<u32>, page: u32, collections: Vec<u32>) -> RecordedCall {
+        let contract = test::callee::<CustomEnvironment>();
+        RecordedCall::new(0xbb93, (contract, start_after, page), collections)
+    }
+
+    #[ink::test]
+    fn sync_picks_up_collections_created_outside_the_contract() {
+        let replay = Replay::register(vec![
+            page(None, 2, vec![3, 5]),
+            page(Some(5), 2, vec![8]),
+            page(Some(8), 2, vec![]),
+        ]);
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.sync(None, 2).ok(), Some(Some(5)));
+        assert_eq!(contract.sync(Some(5), 2).ok(), Some(Some(8)));
+        // The collections found are only tracked once the sync completed.
+        assert_eq!(contract.tracked_collections(), Vec::<u32>::new());
+        assert_eq!(contract.sync(Some(8), 2).ok(), Some(None));
+        assert_eq!(contract.tracked_collections(), vec![3, 5, 8]);
+        assert_eq!(test::recorded_events().count(), 3);
+        replay.assert_finished();
+    }
+
+    #[ink::test]
+    fn sync_drops_collections_the_contract_no_longer_owns() {
+        let replay = Replay::register(vec![
+            page(None, 10, vec![3, 5]),
+            page(Some(5), 10, vec![]),
+            // Collection 3 was destroyed and collection 9 handed to the contract meanwhile.
+            page(None, 10, vec![5, 9]),
+            page(Some(9), 10, vec![]),
+        ]);
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.sync(None, 10).ok(), Some(Some(5)));
+        assert_eq!(contract.sync(Some(5), 10).ok(), Some(None));
+        assert_eq!(contract.sync(None, 10).ok(), Some(Some(9)));
+        assert_eq!(contract.sync(Some(9), 10).ok(), Some(None));
+        assert_eq!(contract.tracked_collections(), vec![5, 9]);
+        // Collections 3 and 5 were added, then 3 dropped and 9 added.
+        assert_eq!(test::recorded_events().count(), 4);
+        replay.assert_finished();
+    }
+
+    #[ink::test]
+    fn sync_continues_only_where_it_stopped() {
+        let replay = Replay::register(vec![
+            page(None, 1, vec![3]),
+            page(Some(3), 1, vec![]),
+            page(None, 1, vec![4]),
+            // Starting over discards the collections found by the interrupted sync.
+            page(None, 1, vec![5]),
+            page(Some(5), 1, vec![]),
+        ]);
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.sync(None, 1).ok(), Some(Some(3)));
+        assert!(matches!(contract.sync(Some(2), 1), Err(Psp02Error::SyncOutOfOrder)));
+        assert_eq!(contract.sync(Some(3), 1).ok(), Some(None));
+        // A completed sync cannot be continued, as it would miss the collections before.
+        assert!(matches!(contract.sync(Some(3), 1), Err(Psp02Error::SyncOutOfOrder)));
+        assert_eq!(contract.sync(None, 1).ok(), Some(Some(4)));
+        assert_eq!(contract.tracked_collections(), vec![3]);
+        assert_eq!(contract.sync(None, 1).ok(), Some(Some(5)));
+        assert_eq!(contract.sync(Some(5), 1).ok(), Some(None));
+        assert_eq!(contract.tracked_collections(), vec![5]);
+        replay.assert_finished();
     }
 }
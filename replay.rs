@@ -0,0 +1,267 @@
+//! Replays recorded chain extension calls in ink!'s off-chain test environment.
+//!
+//! A recording is the sequence of calls a contract made to the extension, each along with
+//! the status code and output the runtime answered with. Runtimes built with the pallet's
+//! `debug` feature note every call in the debug buffer of dry runs, which
+//! [`Replay::register_debug_message`] takes, so dry running the calls of an e2e run or of
+//! an incident against such a node captures them. Nothing is stored on chain, so calls
+//! included in blocks are not recorded. Replaying a recording answers the contract's calls
+//! with the recorded results and checks that the contract makes exactly the recorded calls
+//! in the recorded order, which turns the incident into a deterministic `#[ink::test]`.
+
+use ink::env::test::ChainExtension;
+use scale::Decode;
+use std::{
+    cell::RefCell,
+    collections::{
+        BTreeSet,
+        VecDeque,
+    },
+    rc::Rc,
+};
+
+/// A call to the chain extension along with the runtime's answer.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+pub struct RecordedCall {
+    pub func_id: u32,
+    /// The SCALE encoded input of the call.
+    pub input: Vec<u8>,
+    pub status: u32,
+    /// The SCALE encoded output of the call.
+    pub output: Vec<u8>,
+}
+
+impl RecordedCall {
+    /// Records a successful call to `func_id` with `input` that returned `output`.
+    pub fn new(func_id: u32, input: impl scale::Encode, output: impl scale::Encode) -> Self {
+        Self { func_id, input: input.encode(), status: 0, output: output.encode() }
+    }
+
+    /// Records a call to `func_id` with `input` that failed with `status`.
+    pub fn failed(func_id: u32, input: impl scale::Encode, status: u32) -> Self {
+        Self { func_id, input: input.encode(), status, output: Vec::new() }
+    }
+}
+
+/// A recording being replayed in the off-chain environment.
+#[derive(Clone)]
+pub struct Replay {
+    calls: Rc<RefCell<VecDeque<RecordedCall>>>,
+}
+
+impl Replay {
+    /// Registers the function IDs of `calls` in the off-chain environment and answers
+    /// them from `calls`, in order.
+    pub fn register(calls: Vec<RecordedCall>) -> Self {
+        let func_ids: BTreeSet<u32> = calls.iter().map(|call| call.func_id).collect();
+        let replay = Self { calls: Rc::new(RefCell::new(calls.into())) };
+        for func_id in func_ids {
+            ink::env::test::register_chain_extension(Handler {
+                func_id,
+                replay: replay.clone(),
+            });
+        }
+        replay
+    }
+
+    /// Decodes a SCALE encoded `Vec<RecordedCall>` and registers it.
+    pub fn register_encoded(mut recording: &[u8]) -> Result<Self, scale::Error> {
+        Vec::<RecordedCall>::decode(&mut recording).map(Self::register)
+    }
+
+    /// Registers the calls noted in the debug message of a dry run against a runtime built
+    /// with the pallet's `debug` feature, skipping any other lines of the message.
+    pub fn register_debug_message(message: &str) -> Result<Self, &'static str> {
+        let mut calls: Vec<RecordedCall> = Vec::new();
+        for line in message.lines() {
+            let Some((label, parts)) = line.strip_prefix("psp02 ").and_then(|n| n.split_once(':'))
+            else {
+                continue
+            };
+            let parts = parts.split_whitespace().map(decode_hex).collect::<Result<Vec<_>, _>>()?;
+            match (label, &parts[..]) {
+                ("call", [func_id, input]) => {
+                    let func_id: [u8; 2] = func_id[..].try_into().map_err(|_| "bad function ID")?;
+                    calls.push(RecordedCall {
+                        func_id: u16::from_be_bytes(func_id).into(),
+                        input: input.clone(),
+                        status: 0,
+                        output: Vec::new(),
+                    });
+                }
+                ("output", [output]) => {
+                    let call = calls.last_mut().ok_or("output noted before any call")?;
+                    call.output = output.clone();
+                }
+                ("status", [status]) => {
+                    let status: [u8; 4] = status[..].try_into().map_err(|_| "bad status code")?;
+                    let call = calls.last_mut().ok_or("status noted before any call")?;
+                    call.status = u32::from_be_bytes(status);
+                }
+                _ => return Err("unknown note"),
+            }
+        }
+        Ok(Self::register(calls))
+    }
+
+    /// Returns the number of recorded calls the contract did not make yet.
+    pub fn remaining(&self) -> usize {
+        self.calls.borrow().len()
+    }
+
+    /// Panics unless the contract made every recorded call.
+    pub fn assert_finished(&self) {
+        let remaining = self.calls.borrow();
+        assert!(remaining.is_empty(), "recorded calls were not made: {:?}", remaining);
+    }
+}
+
+/// Decodes `0x` prefixed hex as noted in the debug buffer.
+fn decode_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+    let hex = hex.strip_prefix("0x").ok_or("hex without 0x prefix")?;
+    if hex.len() % 2 != 0 {
+        return Err("hex of odd length")
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "invalid hex digit"))
+        .collect()
+}
+
+/// Answers the calls to one function ID from the shared recording.
+struct Handler {
+    func_id: u32,
+    replay: Replay,
+}
+
+impl ChainExtension for Handler {
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // The off-chain engine encodes the already encoded input once more.
+        let input = Vec::<u8>::decode(&mut &input[..])
+            .expect("the off-chain engine passes the input as encoded bytes");
+        let expected = self.replay.calls.borrow_mut().pop_front().unwrap_or_else(|| {
+            panic!("unexpected call to {:#x} after the end of the recording", self.func_id)
+        });
+        assert_eq!(
+            (self.func_id, &input),
+            (expected.func_id, &expected.input),
+            "the contract diverged from the recording"
+        );
+        output.extend(expected.output);
+        expected.status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        psp02_ext::Psp02Extension,
+        DefaultAccountId,
+        EncodedError,
+        LastError,
+        Psp02Error,
+    };
+
+    fn account(byte: u8) -> DefaultAccountId {
+        DefaultAccountId::from([byte; 32])
+    }
+
+    #[ink::test]
+    fn replays_recorded_answers() {
+        let replay = Replay::register(vec![
+            RecordedCall::new(0x162d, (0u32, 42u32), Some(account(1))),
+            RecordedCall::new(0xdb20, (0u32, 42u32, account(2)), ()),
+            RecordedCall::new(0xb65e, (), Some(account(3))),
+            RecordedCall::failed(0xdb20, (0u32, 42u32, account(2)), 3),
+        ]);
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.get_owner(0, 42).ok(), Some(Some(account(1))));
+        assert!(contract.transfer_nft(42, account(2), 0).is_ok());
+        assert!(matches!(
+            contract.transfer_nft(42, account(2), 0),
+            Err(Psp02Error::TransferToOwner)
+        ));
+        let events: Vec<_> = ink::env::test::recorded_events().collect();
+        assert_eq!(events.len(), 1);
+        // The item was transferred from the user the contract acts for.
+        assert!(events[0].data.ends_with(&scale::Encode::encode(&(account(3), account(2)))));
+        replay.assert_finished();
+    }
+
+    #[ink::test]
+    fn replays_encoded_recordings() {
+        let recording = vec![RecordedCall::new(0x32ae, 0u32, Some(7u32))];
+        let replay = Replay::register_encoded(&scale::Encode::encode(&recording)).unwrap();
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.next_free_item_id(0).ok(), Some(Some(7)));
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[ink::test]
+    fn replays_debug_messages() {
+        let message = "psp02 call: 0x32ae 0x00000000\n\
+            psp02 output: 0x0107000000\n\
+            psp02 status: 0x00000000\n\
+            psp02 call: 0xdb20 0x0000000000000000\n";
+        let replay = Replay::register_debug_message(message).unwrap();
+        assert_eq!(replay.remaining(), 2);
+        let mut contract = Psp02Extension::new();
+
+        assert_eq!(contract.next_free_item_id(0).ok(), Some(Some(7)));
+        assert_eq!(replay.remaining(), 1);
+        let error = Replay::register_debug_message("psp02 status: 0x00").err();
+        assert_eq!(error, Some("bad status code"));
+    }
+
+    #[ink::test]
+    fn reports_failed_calls_of_debug_runtimes() {
+        // `Module { index: 9, error: [2, 0, 0, 0] }` of the runtime.
+        let error = EncodedError(vec![3, 9, 2, 0, 0, 0]);
+        let last_error = LastError { func_id: 0xd123, error };
+        Replay::register(vec![
+            RecordedCall::failed(0xd123, 0u32, 5),
+            RecordedCall::new(0x1ae3, (), Some(last_error.clone())),
+        ]);
+        let mut contract = Psp02Extension::new();
+
+        assert!(matches!(contract.cancel_admin_op(0), Err(Psp02Error::CallFailed)));
+        assert_eq!(contract.last_error().ok(), Some(Some(last_error)));
+    }
+
+    #[cfg(feature = "defensive")]
+    #[ink::test]
+    fn unexpected_answers_become_errors() {
+        Replay::register(vec![
+            RecordedCall::failed(0x162d, (0u32, 42u32), 99),
+            RecordedCall { output: vec![7], ..RecordedCall::new(0x162d, (0u32, 42u32), ()) },
+        ]);
+        let contract = Psp02Extension::new();
+
+        assert!(matches!(contract.get_owner(0, 42), Err(Psp02Error::UnknownStatusCode(99))));
+        assert!(matches!(contract.get_owner(0, 42), Err(Psp02Error::DecodeFailed)));
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "the contract diverged from the recording")]
+    fn panics_on_divergence() {
+        Replay::register(vec![RecordedCall::new(0x162d, (0u32, 42u32), None::<DefaultAccountId>)]);
+        let contract = Psp02Extension::new();
+
+        let _ = contract.get_owner(0, 43);
+    }
+
+    #[ink::test]
+    #[should_panic(expected = "recorded calls were not made")]
+    fn panics_on_missing_calls() {
+        let replay = Replay::register(vec![RecordedCall::new(0x162d, (0u32, 42u32), ())]);
+
+        replay.assert_finished();
+    }
+}
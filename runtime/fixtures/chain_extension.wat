@@ -0,0 +1,48 @@
+;; Forwards its input to the chain extension and returns the result.
+;;
+;; Input:  [func_id: u32][payload]
+;; Output: [ret_val: u32][output of the chain extension]
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 16 16))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\00\40")
+
+	;; [4, 16388) input buffer
+
+	;; [16388, 16392) length of the output buffer
+	(data (i32.const 16388) "\00\40")
+
+	;; [16392, 16396) return value of the chain extension
+
+	;; [16396, 32780) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+
+		(i32.store
+			(i32.const 16392)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))                         ;; func_id
+				(i32.const 8)                                    ;; input_ptr
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4)) ;; input_len
+				(i32.const 16396)                                ;; output_ptr
+				(i32.const 16388)                                ;; output_len_ptr
+			)
+		)
+
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 16392)
+			(i32.add (i32.load (i32.const 16388)) (i32.const 4))
+		)
+		(unreachable)
+	)
+)
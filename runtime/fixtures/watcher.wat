@@ -0,0 +1,43 @@
+;; Records the input of its last call in storage, under the all-zero key, and the caller of
+;; its last call under the key of all ones.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_caller" (func $seal_caller (param i32 i32)))
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key of the input
+
+	;; [32, 36) length of the input buffer
+	(data (i32.const 32) "\00\01")
+
+	;; [36, 292) input buffer
+
+	;; [292, 324) storage key of the caller
+	(data (i32.const 292)
+		"\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01"
+		"\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01"
+	)
+
+	;; [324, 328) length of the caller buffer
+	(data (i32.const 324) "\20")
+
+	;; [328, 360) caller buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 36) (i32.const 32))
+		(call $seal_set_storage
+			(i32.const 0)              ;; key_ptr
+			(i32.const 36)             ;; value_ptr
+			(i32.load (i32.const 32))  ;; value_len
+		)
+		(call $seal_caller (i32.const 328) (i32.const 324))
+		(call $seal_set_storage
+			(i32.const 292)            ;; key_ptr
+			(i32.const 328)            ;; value_ptr
+			(i32.const 32)             ;; value_len
+		)
+	)
+)
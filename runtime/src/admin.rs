@@ -0,0 +1,137 @@
+//! Admin operations gated by a second confirmation.
+//!
+//! Once a collection owner configures a confirmer, destroying the collection, forcing
+//! transfers of its items and changing the confirmer require the confirmer to approve
+//! the operation within `Config::ConfirmationPeriod` blocks. A single compromised key
+//! can therefore no longer wipe out a contract-owned collection.
+//!
+//! `transfer` only moves items the contract may transfer through the NFT pallet's
+//! extrinsic, so forced transfers are the only way for the owner to move items it does
+//! not hold. `pallet_uniques` also lets the admin of a collection transfer any of its
+//! items, so there the confirmer only guards forced transfers once the admin role is
+//! handed to another account.
+
+use frame_support::pallet_prelude::*;
+use sp_runtime::traits::Saturating;
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Executes `op` right away if `collection` has no confirmer, or records it as pending.
+    ///
+    /// Returns the weight [`Pallet::execute_admin_op`] did not need if the operation was
+    /// executed.
+    pub(crate) fn propose_admin_op(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        op: AdminOpOf<T>,
+    ) -> Result<Option<Weight>, DispatchError> {
+        ensure!(
+            T::Backend::collection_owner(collection).as_ref() == Some(who),
+            Error::<T>::NotCollectionOwner
+        );
+        if !AdminConfirmers::<T>::contains_key(collection) {
+            return Self::execute_admin_op(who, collection, op).map(Some)
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        if let Some(pending) = PendingAdminOps::<T>::get(collection) {
+            ensure!(pending.expires_at < now, Error::<T>::AdminOpPending);
+        }
+        PendingAdminOps::<T>::insert(
+            collection,
+            PendingAdminOp {
+                op,
+                proposer: who.clone(),
+                expires_at: now.saturating_add(T::ConfirmationPeriod::get()),
+            },
+        );
+        Ok(None)
+    }
+
+    /// Executes the operation pending on `collection`, which `who` must be the confirmer of.
+    ///
+    /// Returns the weight [`Pallet::execute_admin_op`] did not need.
+    pub(crate) fn confirm_admin_op(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+    ) -> Result<Weight, DispatchError> {
+        ensure!(
+            AdminConfirmers::<T>::get(collection).as_ref() == Some(who),
+            Error::<T>::NotConfirmer
+        );
+        let pending = PendingAdminOps::<T>::take(collection).ok_or(Error::<T>::NoPendingAdminOp)?;
+        ensure!(
+            frame_system::Pallet::<T>::block_number() <= pending.expires_at,
+            Error::<T>::AdminOpExpired
+        );
+        // The collection may have changed hands since the operation was proposed.
+        ensure!(
+            T::Backend::collection_owner(collection).as_ref() == Some(&pending.proposer),
+            Error::<T>::NotCollectionOwner
+        );
+        Self::execute_admin_op(&pending.proposer, collection, pending.op)
+    }
+
+    /// Drops the operation pending on `collection`; `who` must be its proposer or confirmer.
+    pub(crate) fn cancel_admin_op(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+    ) -> DispatchResult {
+        let pending = PendingAdminOps::<T>::get(collection).ok_or(Error::<T>::NoPendingAdminOp)?;
+        ensure!(
+            &pending.proposer == who ||
+                AdminConfirmers::<T>::get(collection).as_ref() == Some(who),
+            Error::<T>::NotConfirmer
+        );
+        PendingAdminOps::<T>::remove(collection);
+        Ok(())
+    }
+
+    /// Returns the weight of the backend call executing `op` on `collection`, plus the
+    /// weight of its bookkeeping in the worst case.
+    pub(crate) fn admin_op_weight(collection: &CollectionIdOf<T>, op: &AdminOpOf<T>) -> Weight {
+        match op {
+            // Noting the destruction and forgetting the watcher of the collection.
+            AdminOp::Destroy => T::Backend::destroy_weight(collection)
+                .saturating_add(T::DbWeight::get().reads_writes(1, 3)),
+            AdminOp::ForceTransfer { .. } => T::Backend::transfer_weight()
+                .saturating_add(T::WeightInfo::forget_item(T::MaxDelegates::get())),
+            AdminOp::SetConfirmer(_) => Weight::zero(),
+        }
+    }
+
+    /// Executes `op` on `collection` on behalf of its `owner`.
+    ///
+    /// Returns the part of [`Pallet::admin_op_weight`] the bookkeeping of `op` turned out
+    /// not to need.
+    pub(crate) fn execute_admin_op(
+        owner: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        op: AdminOpOf<T>,
+    ) -> Result<Weight, DispatchError> {
+        match op {
+            AdminOp::Destroy => {
+                T::Backend::destroy(owner, collection)?;
+                AdminConfirmers::<T>::remove(collection);
+                PendingAdminOps::<T>::remove(collection);
+                Self::note_destroyed(collection);
+            },
+            AdminOp::ForceTransfer { item, dest } => {
+                let from = T::Backend::owner(collection, &item);
+                T::Backend::force_transfer(collection, &item, &dest)?;
+                let delegates = from.map_or(0, |from| {
+                    Self::note_extension_transfer(collection, &item, &from, &dest);
+                    Self::forget_item(&from, collection, &item)
+                });
+                return Ok(T::WeightInfo::forget_item(T::MaxDelegates::get())
+                    .saturating_sub(T::WeightInfo::forget_item(delegates)))
+            },
+            AdminOp::SetConfirmer(confirmer) => {
+                ensure!(confirmer.as_ref() != Some(owner), Error::<T>::ConfirmerIsOwner);
+                AdminConfirmers::<T>::set(collection, confirmer);
+            },
+        }
+        Ok(Weight::zero())
+    }
+}
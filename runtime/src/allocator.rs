@@ -0,0 +1,52 @@
+//! Allocation of unused item ids.
+//!
+//! Contracts minting items used to keep their own id counters, which drift as soon as
+//! items get minted through extrinsics. The extension instead keeps a cursor per
+//! collection in [`pallet::NextItemIds`] and looks up the backend from there, so ids
+//! taken by any means are skipped. Contracts can also have the extension pick the id
+//! when minting, which rules out collisions altogether.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the first unused item id of `collection` from its cursor on, and moves the
+    /// cursor there.
+    ///
+    /// Gives up after looking at `Config::MaxItemIdScan` ids, keeping the progress for the
+    /// next call, and returns `None` for collections that do not exist.
+    pub(crate) fn next_free_item_id(collection: &CollectionIdOf<T>) -> Option<ItemIdOf<T>> {
+        T::Backend::collection_owner(collection)?;
+        let mut id = NextItemIds::<T>::get(collection).unwrap_or_else(Incrementable::initial_value);
+        let mut free = None;
+        for _ in 0..T::MaxItemIdScan::get() {
+            if T::Backend::owner(collection, &id).is_none() {
+                free = Some(id);
+                break
+            }
+            match id.increment() {
+                Some(next) => id = next,
+                None => break,
+            }
+        }
+        NextItemIds::<T>::insert(collection, id);
+        free
+    }
+
+    /// Mints the first unused item id of `collection` to `owner` on behalf of `who`, and
+    /// moves the cursor past it.
+    pub(crate) fn mint_next(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        owner: &T::AccountId,
+    ) -> Result<ItemIdOf<T>, DispatchError> {
+        let item = Self::next_free_item_id(collection).ok_or(Error::<T>::NoFreeItemId)?;
+        T::Backend::mint(who, collection, &item, owner)?;
+        // The content hash of an earlier item with this id does not carry over.
+        Provenance::<T>::remove(collection, item);
+        // The last id stays the cursor once taken, so later searches give up right away.
+        if let Some(next) = item.increment() {
+            NextItemIds::<T>::insert(collection, next);
+        }
+        Ok(item)
+    }
+}
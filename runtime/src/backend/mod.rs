@@ -0,0 +1,197 @@
+//! Abstraction over the NFT pallet the extension is operating on.
+//!
+//! The chain extension only talks to the NFT pallet through [`NftBackend`], so the same
+//! function IDs and contract-side API work against either `pallet_uniques` or
+//! `pallet_nfts`. The backend is selected through the `Backend` associated type of the
+//! extension's `Config`; which implementations are available depends on the `uniques`
+//! and `nfts` Cargo features.
+
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::*,
+};
+use sp_std::vec::Vec;
+
+use crate::Incrementable;
+
+#[cfg(feature = "uniques")]
+mod uniques;
+#[cfg(feature = "nfts")]
+mod nfts;
+
+#[cfg(feature = "uniques")]
+pub use self::uniques::Uniques;
+#[cfg(feature = "nfts")]
+pub use self::nfts::Nfts;
+
+/// Operations the chain extension needs from the underlying NFT pallet.
+///
+/// Every mutating call but [`NftBackend::force_transfer`] is performed on behalf of `who`,
+/// normally the calling contract, and is subject to the same permission checks as the
+/// corresponding extrinsic.
+pub trait NftBackend<AccountId> {
+    /// Identifier of a collection.
+    type CollectionId: Member + Parameter + MaxEncodedLen + Copy;
+    /// Identifier of an item within a collection.
+    type ItemId: Member + Parameter + MaxEncodedLen + Copy + Incrementable;
+
+    /// Whether approving a delegate replaces the previous delegate of the item.
+    const REPLACES_APPROVAL: bool;
+
+    /// Returns the owner of `item` in `collection`, if it exists.
+    fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<AccountId>;
+
+    /// Returns the owner of `collection`, if it exists.
+    fn collection_owner(collection: &Self::CollectionId) -> Option<AccountId>;
+
+    /// Returns whether `collection` was force-created with free holding, i.e. minting its
+    /// items reserves no deposit, if it exists and the backend can tell.
+    fn free_holding(collection: &Self::CollectionId) -> Option<bool>;
+
+    /// Returns up to `limit` collections owned by `who`, following `start_after`.
+    ///
+    /// Collections come in the order of the backend's index of owned collections, which
+    /// is stable but not sorted by id, so pages are fetched by passing the last
+    /// collection of the previous page as `start_after`.
+    fn collections_owned(
+        who: &AccountId,
+        start_after: Option<&Self::CollectionId>,
+        limit: u32,
+    ) -> Vec<Self::CollectionId>;
+
+    /// Returns up to `limit` items of `collection` along with their owners, following
+    /// `start_after`.
+    ///
+    /// Items come in the order of the backend's storage, which is stable but not sorted by
+    /// id, so the items are walked by passing the last item returned as `start_after`.
+    fn items_of(
+        collection: &Self::CollectionId,
+        start_after: Option<&Self::ItemId>,
+        limit: u32,
+    ) -> Vec<(Self::ItemId, AccountId)>;
+
+    /// Returns whether `delegate` is currently approved to transfer `item`.
+    fn is_approved(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &AccountId,
+    ) -> bool;
+
+    /// Returns whether transfers of `item` are currently disabled.
+    fn is_frozen(collection: &Self::CollectionId, item: &Self::ItemId) -> bool;
+
+    /// Moves `item` to `dest` on behalf of `who`, who must own the item, be approved to
+    /// transfer it or, with `pallet_uniques`, be the admin of its collection.
+    fn transfer(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &AccountId,
+    ) -> DispatchResult;
+
+    /// Moves `item` to `dest` regardless of its owner, as forced by the collection owner
+    /// through an admin operation.
+    fn force_transfer(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &AccountId,
+    ) -> DispatchResult;
+
+    /// Mints `item` of `collection` to `owner` on behalf of `who`, the collection's issuer.
+    fn mint(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        owner: &AccountId,
+    ) -> DispatchResult;
+
+    /// Approves `delegate` to transfer `item` on behalf of `who`.
+    ///
+    /// `pallet_uniques` keeps a single delegate per item, so approving a new delegate
+    /// replaces the previous one. `pallet_nfts` keeps every approval until cancelled.
+    fn approve_transfer(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &AccountId,
+    ) -> DispatchResult;
+
+    /// Cancels the approval of `delegate` for `item`.
+    fn cancel_approval(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &AccountId,
+    ) -> DispatchResult;
+
+    /// Sets the metadata of `item`, leaving it mutable.
+    ///
+    /// Locking metadata is a separate operation in `pallet_nfts`, so the extension never
+    /// freezes it as part of this call on either backend.
+    fn set_metadata(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        data: Vec<u8>,
+    ) -> DispatchResult;
+
+    /// Clears the metadata of `item`.
+    fn clear_metadata(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult;
+
+    /// Disables transfers of `item`.
+    fn freeze(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult;
+
+    /// Re-enables transfers of `item`.
+    fn thaw(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult;
+
+    /// Burns `item`, which must be owned by `who` or of a collection `who` administers.
+    fn burn(
+        who: &AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult;
+
+    /// Destroys `collection`, which must be owned by `who`, along with all of its items.
+    fn destroy(who: &AccountId, collection: &Self::CollectionId) -> DispatchResult;
+
+    /// Creates the `index`th benchmarked collection, owned by `owner`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_collection(owner: &AccountId, index: u16) -> Self::CollectionId;
+
+    /// Creates a collection owned by `owner` and mints an item of it to `owner`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_item(owner: &AccountId) -> (Self::CollectionId, Self::ItemId);
+
+    /// Weight of [`NftBackend::transfer`] and [`NftBackend::force_transfer`].
+    fn transfer_weight() -> Weight;
+    /// Weight of [`NftBackend::mint`].
+    fn mint_weight() -> Weight;
+    /// Weight of [`NftBackend::approve_transfer`].
+    fn approve_transfer_weight() -> Weight;
+    /// Weight of [`NftBackend::cancel_approval`].
+    fn cancel_approval_weight() -> Weight;
+    /// Weight of [`NftBackend::set_metadata`].
+    fn set_metadata_weight() -> Weight;
+    /// Weight of [`NftBackend::clear_metadata`].
+    fn clear_metadata_weight() -> Weight;
+    /// Weight of [`NftBackend::freeze`].
+    fn freeze_weight() -> Weight;
+    /// Weight of [`NftBackend::thaw`].
+    fn thaw_weight() -> Weight;
+    /// Weight of [`NftBackend::burn`].
+    fn burn_weight() -> Weight;
+    /// Weight of [`NftBackend::destroy`] for the current size of `collection`.
+    fn destroy_weight(collection: &Self::CollectionId) -> Weight;
+}
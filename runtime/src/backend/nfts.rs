@@ -0,0 +1,361 @@
+use frame_support::{
+    dispatch::{
+        DispatchResult,
+        RawOrigin,
+    },
+    pallet_prelude::*,
+    traits::tokens::nonfungibles_v2::{
+        Destroy,
+        Inspect,
+    },
+};
+#[cfg(feature = "runtime-benchmarks")]
+use frame_support::traits::Currency;
+use pallet_nfts::WeightInfo;
+use sp_runtime::traits::StaticLookup;
+use sp_std::{
+    collections::btree_map::BTreeMap,
+    vec::Vec,
+};
+
+use super::NftBackend;
+use crate::Incrementable;
+
+/// The collections owned by each account, as indexed by `pallet_nfts`.
+#[frame_support::storage_alias]
+type CollectionAccount<T: pallet_nfts::Config> = StorageDoubleMap<
+    pallet_nfts::Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Blake2_128Concat,
+    <T as pallet_nfts::Config>::CollectionId,
+    (),
+>;
+
+/// The balance of the currency `pallet_nfts` reserves deposits in.
+#[cfg(feature = "runtime-benchmarks")]
+type BalanceOf<T> = <<T as pallet_nfts::Config>::Currency as Currency<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// The leading fields of the item details stored by `pallet_nfts`, which keeps them
+/// private. Decoding stops after `approvals` and ignores the remaining fields.
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+struct ItemDetailsPrefix<AccountId, BlockNumber> {
+    owner: AccountId,
+    approvals: BTreeMap<AccountId, Option<BlockNumber>>,
+}
+
+/// The details of each item, as stored by `pallet_nfts`, read up to `approvals`.
+#[frame_support::storage_alias]
+type Item<T: pallet_nfts::Config> = StorageDoubleMap<
+    pallet_nfts::Pallet<T>,
+    Blake2_128Concat,
+    <T as pallet_nfts::Config>::CollectionId,
+    Blake2_128Concat,
+    <T as pallet_nfts::Config>::ItemId,
+    ItemDetailsPrefix<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    >,
+>;
+
+/// [`NftBackend`] backed by `pallet_nfts`.
+///
+/// Approvals are granted without a deadline, and metadata is never locked by the
+/// extension; locking is left to the collection's dedicated extrinsics. Freezing an
+/// item maps to locking its transfers. Free holding is not reported, as `pallet_nfts`
+/// replaced it with collection settings it does not expose.
+pub struct Nfts<T>(PhantomData<T>);
+
+impl<T: pallet_nfts::Config> NftBackend<T::AccountId> for Nfts<T>
+where
+    T::ItemId: Incrementable,
+{
+    type CollectionId = T::CollectionId;
+    type ItemId = T::ItemId;
+
+    const REPLACES_APPROVAL: bool = false;
+
+    fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+        pallet_nfts::Pallet::<T>::owner(*collection, *item)
+    }
+
+    fn collection_owner(collection: &Self::CollectionId) -> Option<T::AccountId> {
+        <pallet_nfts::Pallet<T> as Inspect<T::AccountId>>::collection_owner(collection)
+    }
+
+    fn free_holding(_collection: &Self::CollectionId) -> Option<bool> {
+        None
+    }
+
+    fn collections_owned(
+        who: &T::AccountId,
+        start_after: Option<&Self::CollectionId>,
+        limit: u32,
+    ) -> Vec<Self::CollectionId> {
+        let collections = match start_after {
+            Some(collection) => CollectionAccount::<T>::iter_key_prefix_from(
+                who,
+                CollectionAccount::<T>::hashed_key_for(who, collection),
+            ),
+            None => CollectionAccount::<T>::iter_key_prefix(who),
+        };
+        collections.take(limit as usize).collect()
+    }
+
+    fn items_of(
+        collection: &Self::CollectionId,
+        start_after: Option<&Self::ItemId>,
+        limit: u32,
+    ) -> Vec<(Self::ItemId, T::AccountId)> {
+        let items = match start_after {
+            Some(item) => Item::<T>::iter_prefix_from(
+                collection,
+                Item::<T>::hashed_key_for(collection, item),
+            ),
+            None => Item::<T>::iter_prefix(collection),
+        };
+        items.take(limit as usize).map(|(item, details)| (item, details.owner)).collect()
+    }
+
+    fn is_approved(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> bool {
+        // Approvals past their deadline stay stored until cancelled.
+        let now = frame_system::Pallet::<T>::block_number();
+        Item::<T>::get(collection, item)
+            .and_then(|details| details.approvals.get(delegate).copied())
+            .map_or(false, |deadline| deadline.map_or(true, |deadline| now <= deadline))
+    }
+
+    fn is_frozen(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+        !<pallet_nfts::Pallet<T> as Inspect<T::AccountId>>::can_transfer(collection, item)
+    }
+
+    fn transfer(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(dest.clone()),
+        )
+    }
+
+    fn force_transfer(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::do_transfer(*collection, *item, dest.clone(), |_, _| Ok(()))
+    }
+
+    fn mint(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        owner: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::mint(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(owner.clone()),
+        )
+    }
+
+    fn approve_transfer(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::approve_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(delegate.clone()),
+            None,
+        )
+    }
+
+    fn cancel_approval(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::cancel_approval(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(delegate.clone()),
+        )
+    }
+
+    fn set_metadata(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        data: Vec<u8>,
+    ) -> DispatchResult {
+        let data = data
+            .try_into()
+            .map_err(|_| DispatchError::Other("Metadata exceeds StringLimit"))?;
+        pallet_nfts::Pallet::<T>::set_metadata(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            data,
+        )
+    }
+
+    fn clear_metadata(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::clear_metadata(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn freeze(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::lock_item_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn thaw(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::unlock_item_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn burn(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_nfts::Pallet::<T>::burn(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            None,
+        )
+    }
+
+    fn destroy(who: &T::AccountId, collection: &Self::CollectionId) -> DispatchResult {
+        let witness =
+            <pallet_nfts::Pallet<T> as Destroy<T::AccountId>>::get_destroy_witness(collection)
+                .ok_or(pallet_nfts::Error::<T>::UnknownCollection)?;
+        <pallet_nfts::Pallet<T> as Destroy<T::AccountId>>::destroy(
+            *collection,
+            witness,
+            Some(who.clone()),
+        )
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_collection(owner: &T::AccountId, index: u16) -> Self::CollectionId {
+        use frame_support::traits::EnsureOrigin;
+        use pallet_nfts::BenchmarkHelper;
+
+        use sp_runtime::traits::Bounded;
+
+        // The default configuration leaves every setting enabled and has minting reserve
+        // deposits, so the owner is funded for its mints.
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        T::Currency::make_free_balance_be(owner, balance);
+        let collection = T::Helper::collection(index);
+        pallet_nfts::Pallet::<T>::force_create(
+            T::ForceOrigin::successful_origin(),
+            collection,
+            T::Lookup::unlookup(owner.clone()),
+            Default::default(),
+        )
+        .expect("the force origin can create collections");
+        collection
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_item(owner: &T::AccountId) -> (Self::CollectionId, Self::ItemId) {
+        use pallet_nfts::BenchmarkHelper;
+
+        let collection = Self::create_collection(owner, 0);
+        let item = T::Helper::item(0);
+        Self::mint(owner, &collection, &item, owner).expect("the collection owner can mint");
+        (collection, item)
+    }
+
+    fn transfer_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::transfer()
+    }
+
+    fn mint_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::mint()
+    }
+
+    fn approve_transfer_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::approve_transfer()
+    }
+
+    fn cancel_approval_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::cancel_approval()
+    }
+
+    fn set_metadata_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::set_metadata()
+    }
+
+    fn clear_metadata_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::clear_metadata()
+    }
+
+    fn freeze_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::lock_item_transfer()
+    }
+
+    fn thaw_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::unlock_item_transfer()
+    }
+
+    fn burn_weight() -> Weight {
+        <T as pallet_nfts::Config>::WeightInfo::burn()
+    }
+
+    fn destroy_weight(collection: &Self::CollectionId) -> Weight {
+        <pallet_nfts::Pallet<T> as Destroy<T::AccountId>>::get_destroy_witness(collection)
+            .map(|witness| {
+                <T as pallet_nfts::Config>::WeightInfo::destroy(
+                    witness.items,
+                    witness.item_metadatas,
+                    witness.attributes,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
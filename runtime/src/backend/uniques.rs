@@ -0,0 +1,364 @@
+use frame_support::{
+    dispatch::{
+        DispatchResult,
+        RawOrigin,
+    },
+    pallet_prelude::*,
+    traits::{
+        tokens::nonfungibles::{
+            Destroy,
+            Inspect,
+            Transfer,
+        },
+        Currency,
+    },
+};
+use pallet_uniques::WeightInfo;
+use sp_runtime::traits::StaticLookup;
+use sp_std::vec::Vec;
+
+use super::NftBackend;
+use crate::Incrementable;
+
+/// The collections owned by each account, as indexed by `pallet_uniques`.
+#[frame_support::storage_alias]
+type ClassAccount<T: pallet_uniques::Config> = StorageDoubleMap<
+    pallet_uniques::Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Blake2_128Concat,
+    <T as pallet_uniques::Config>::CollectionId,
+    (),
+>;
+
+/// The leading fields of the collection details stored by `pallet_uniques`, which keeps
+/// them private. Decoding stops after `free_holding` and ignores the remaining fields.
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+struct CollectionDetailsPrefix<AccountId, Balance> {
+    owner: AccountId,
+    issuer: AccountId,
+    admin: AccountId,
+    freezer: AccountId,
+    total_deposit: Balance,
+    free_holding: bool,
+}
+
+/// The details of each collection, as stored by `pallet_uniques`, read up to `free_holding`.
+#[frame_support::storage_alias]
+type Class<T: pallet_uniques::Config> = StorageMap<
+    pallet_uniques::Pallet<T>,
+    Blake2_128Concat,
+    <T as pallet_uniques::Config>::CollectionId,
+    CollectionDetailsPrefix<
+        <T as frame_system::Config>::AccountId,
+        <<T as pallet_uniques::Config>::Currency as Currency<
+            <T as frame_system::Config>::AccountId,
+        >>::Balance,
+    >,
+>;
+
+/// The leading fields of the item details stored by `pallet_uniques`, which keeps them
+/// private. Decoding stops after `approved` and ignores the remaining fields.
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+struct ItemDetailsPrefix<AccountId> {
+    owner: AccountId,
+    approved: Option<AccountId>,
+}
+
+/// The details of each item, as stored by `pallet_uniques`, read up to `approved`.
+#[frame_support::storage_alias]
+type Asset<T: pallet_uniques::Config> = StorageDoubleMap<
+    pallet_uniques::Pallet<T>,
+    Blake2_128Concat,
+    <T as pallet_uniques::Config>::CollectionId,
+    Blake2_128Concat,
+    <T as pallet_uniques::Config>::ItemId,
+    ItemDetailsPrefix<<T as frame_system::Config>::AccountId>,
+>;
+
+/// [`NftBackend`] backed by `pallet_uniques`.
+pub struct Uniques<T>(PhantomData<T>);
+
+impl<T: pallet_uniques::Config> NftBackend<T::AccountId> for Uniques<T>
+where
+    T::ItemId: Incrementable,
+{
+    type CollectionId = T::CollectionId;
+    type ItemId = T::ItemId;
+
+    const REPLACES_APPROVAL: bool = true;
+
+    fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+        <pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::owner(collection, item)
+    }
+
+    fn collection_owner(collection: &Self::CollectionId) -> Option<T::AccountId> {
+        <pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::collection_owner(collection)
+    }
+
+    fn free_holding(collection: &Self::CollectionId) -> Option<bool> {
+        Class::<T>::get(collection).map(|details| details.free_holding)
+    }
+
+    fn collections_owned(
+        who: &T::AccountId,
+        start_after: Option<&Self::CollectionId>,
+        limit: u32,
+    ) -> Vec<Self::CollectionId> {
+        let collections = match start_after {
+            Some(collection) => ClassAccount::<T>::iter_key_prefix_from(
+                who,
+                ClassAccount::<T>::hashed_key_for(who, collection),
+            ),
+            None => ClassAccount::<T>::iter_key_prefix(who),
+        };
+        collections.take(limit as usize).collect()
+    }
+
+    fn items_of(
+        collection: &Self::CollectionId,
+        start_after: Option<&Self::ItemId>,
+        limit: u32,
+    ) -> Vec<(Self::ItemId, T::AccountId)> {
+        let items = match start_after {
+            Some(item) => Asset::<T>::iter_prefix_from(
+                collection,
+                Asset::<T>::hashed_key_for(collection, item),
+            ),
+            None => Asset::<T>::iter_prefix(collection),
+        };
+        items.take(limit as usize).map(|(item, details)| (item, details.owner)).collect()
+    }
+
+    fn is_approved(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> bool {
+        Asset::<T>::get(collection, item)
+            .map_or(false, |details| details.approved.as_ref() == Some(delegate))
+    }
+
+    fn is_frozen(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+        !<pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::can_transfer(collection, item)
+    }
+
+    fn transfer(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(dest.clone()),
+        )
+    }
+
+    fn force_transfer(
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        dest: &T::AccountId,
+    ) -> DispatchResult {
+        <pallet_uniques::Pallet<T> as Transfer<T::AccountId>>::transfer(collection, item, dest)
+    }
+
+    fn mint(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        owner: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::mint(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(owner.clone()),
+        )
+    }
+
+    fn approve_transfer(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::approve_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            T::Lookup::unlookup(delegate.clone()),
+        )
+    }
+
+    fn cancel_approval(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        delegate: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::cancel_approval(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            Some(T::Lookup::unlookup(delegate.clone())),
+        )
+    }
+
+    fn set_metadata(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+        data: Vec<u8>,
+    ) -> DispatchResult {
+        let data = data
+            .try_into()
+            .map_err(|_| DispatchError::Other("Metadata exceeds StringLimit"))?;
+        pallet_uniques::Pallet::<T>::set_metadata(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            data,
+            false,
+        )
+    }
+
+    fn clear_metadata(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::clear_metadata(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn freeze(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::freeze(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn thaw(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::thaw(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+        )
+    }
+
+    fn burn(
+        who: &T::AccountId,
+        collection: &Self::CollectionId,
+        item: &Self::ItemId,
+    ) -> DispatchResult {
+        pallet_uniques::Pallet::<T>::burn(
+            RawOrigin::Signed(who.clone()).into(),
+            *collection,
+            *item,
+            None,
+        )
+    }
+
+    fn destroy(who: &T::AccountId, collection: &Self::CollectionId) -> DispatchResult {
+        let witness =
+            <pallet_uniques::Pallet<T> as Destroy<T::AccountId>>::get_destroy_witness(collection)
+                .ok_or(pallet_uniques::Error::<T>::UnknownCollection)?;
+        <pallet_uniques::Pallet<T> as Destroy<T::AccountId>>::destroy(
+            *collection,
+            witness,
+            Some(who.clone()),
+        )
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_collection(owner: &T::AccountId, index: u16) -> Self::CollectionId {
+        use frame_support::traits::EnsureOrigin;
+        use pallet_uniques::BenchmarkHelper;
+
+        let collection = T::Helper::collection(index);
+        pallet_uniques::Pallet::<T>::force_create(
+            T::ForceOrigin::successful_origin(),
+            collection,
+            T::Lookup::unlookup(owner.clone()),
+            true,
+        )
+        .expect("the force origin can create collections");
+        collection
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_item(owner: &T::AccountId) -> (Self::CollectionId, Self::ItemId) {
+        use pallet_uniques::BenchmarkHelper;
+
+        let collection = Self::create_collection(owner, 0);
+        let item = T::Helper::item(0);
+        Self::mint(owner, &collection, &item, owner).expect("the collection owner can mint");
+        (collection, item)
+    }
+
+    fn transfer_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::transfer()
+    }
+
+    fn mint_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::mint()
+    }
+
+    fn approve_transfer_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::approve_transfer()
+    }
+
+    fn cancel_approval_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::cancel_approval()
+    }
+
+    fn set_metadata_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::set_metadata()
+    }
+
+    fn clear_metadata_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::clear_metadata()
+    }
+
+    fn freeze_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::freeze()
+    }
+
+    fn thaw_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::thaw()
+    }
+
+    fn burn_weight() -> Weight {
+        <T as pallet_uniques::Config>::WeightInfo::burn()
+    }
+
+    fn destroy_weight(collection: &Self::CollectionId) -> Weight {
+        <pallet_uniques::Pallet<T> as Destroy<T::AccountId>>::get_destroy_witness(collection)
+            .map(|witness| {
+                <T as pallet_uniques::Config>::WeightInfo::destroy(
+                    witness.items,
+                    witness.item_metadatas,
+                    witness.attributes,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,642 @@
+//! Benchmarks of the work the chain extension does on top of the NFT pallet.
+
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_benchmarking::{
+    account,
+    benchmarks,
+    whitelisted_caller,
+};
+use frame_support::{
+    pallet_prelude::*,
+    traits::Get,
+};
+use sp_runtime::traits::{
+    Hash,
+    TrailingZeroInput,
+};
+use sp_std::{
+    vec,
+    vec::Vec,
+};
+
+use super::*;
+use crate::{
+    output::Output,
+    Pallet as ContractsUniques,
+};
+
+/// Largest metadata the extension is benchmarked with.
+const MAX_METADATA_LEN: u32 = 16 * 1024;
+
+/// Returns the `i`th item of the first collection, whether it exists or not.
+fn ids<T: Config>(i: u32) -> (CollectionIdOf<T>, ItemIdOf<T>) {
+    let collection = Decode::decode(&mut TrailingZeroInput::zeroes())
+        .expect("infinite input is enough to decode an id");
+    let item = Decode::decode(&mut TrailingZeroInput::new(&i.encode()))
+        .expect("infinite input is enough to decode an id");
+    (collection, item)
+}
+
+/// Records that `owner` approved `n` items to `delegate`, without going through the backend.
+fn approve_items<T: Config>(owner: &T::AccountId, delegate: &T::AccountId, n: u32) {
+    let items: Vec<_> = (0..n).map(ids::<T>).collect();
+    Approvals::<T>::insert(
+        owner,
+        delegate,
+        BoundedVec::<_, T::MaxApprovals>::try_from(items).expect("n is at most MaxApprovals"),
+    );
+    if n > 0 {
+        DelegateCounts::<T>::mutate(owner, |count| *count += 1);
+    }
+}
+
+benchmarks! {
+    owner {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+    }: {
+        T::Backend::owner(&collection, &item);
+    }
+
+    transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        TransferCooldowns::<T>::insert(collection, T::BlockNumber::from(1u32));
+        LastTransfers::<T>::insert(collection, item, T::BlockNumber::from(0u32));
+        WatchedOwners::<T>::insert(collection, item, (0, caller.clone()));
+    }: {
+        T::Backend::owner(&collection, &item);
+        ContractsUniques::<T>::is_cooling_down(&collection, &item);
+        ContractsUniques::<T>::note_transfer(&collection, &item);
+        ContractsUniques::<T>::note_extension_transfer(&collection, &item, &caller, &caller);
+    }
+
+    forget_item {
+        let d in 0 .. T::MaxDelegates::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = ids::<T>(0);
+        for i in 0..d {
+            approve_items::<T>(&caller, &account("delegate", i, 0), 1);
+        }
+    }: {
+        ContractsUniques::<T>::forget_item(&caller, &collection, &item);
+    }
+    verify {
+        assert_eq!(Approvals::<T>::iter_key_prefix(&caller).count(), 0);
+    }
+
+    approve {
+        let n in 0 .. T::MaxApprovals::get() - 1;
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        approve_items::<T>(&caller, &delegate, n);
+        let (collection, item) = ids::<T>(n);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::note_approval(&owner, &collection, &item, &delegate)?;
+    }
+    verify {
+        assert_eq!(Approvals::<T>::decode_len(&caller, &delegate), Some(n as usize + 1));
+    }
+
+    cancel_approval {
+        let n in 1 .. T::MaxApprovals::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        approve_items::<T>(&caller, &delegate, n);
+        let (collection, item) = ids::<T>(n - 1);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::forget_approval(&owner, &collection, &item, &delegate);
+    }
+    verify {
+        let approved = Approvals::<T>::decode_len(&caller, &delegate).unwrap_or_default();
+        assert_eq!(approved, n as usize - 1);
+    }
+
+    approvals_to {
+        let n in 0 .. T::MaxApprovals::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        approve_items::<T>(&caller, &delegate, n);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::approvals_to_output(&owner, &delegate);
+    }
+
+    // Baseline of `approvals_to`, collecting the items before encoding them.
+    #[extra]
+    approvals_to_collected {
+        let n in 0 .. T::MaxApprovals::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        approve_items::<T>(&caller, &delegate, n);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::approvals_to(&owner, &delegate).encode();
+    }
+
+    // The items are not owned by the caller, so no backend call is made: its weight is
+    // charged separately for every item.
+    revoke_all_approvals {
+        let n in 0 .. T::MaxApprovals::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        approve_items::<T>(&caller, &delegate, n);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::revoke_all_approvals(&owner, &delegate)?;
+    }
+    verify {
+        assert!(!Approvals::<T>::contains_key(&caller, &delegate));
+    }
+
+    attest_ownership {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+    }: {
+        ContractsUniques::<T>::attest_ownership(&collection, &item);
+    }
+    verify {
+        assert_eq!(Attestations::<T>::iter().count(), 1);
+    }
+
+    verify_attestation {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        let (attestation, _) = ContractsUniques::<T>::attest_ownership(&collection, &item)
+            .expect("the item exists");
+    }: {
+        assert!(ContractsUniques::<T>::verify_attestation(&attestation));
+    }
+
+    set_metadata {
+        let l in 0 .. MAX_METADATA_LEN;
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = ids::<T>(0);
+        let input = (collection, item, vec![0u8; l as usize]).encode();
+    }: {
+        ContractsUniques::<T>::account_of(&caller)?;
+        <(CollectionIdOf<T>, ItemIdOf<T>, Vec<u8>)>::decode(&mut &input[..])
+            .expect("input was encoded from the same type");
+    }
+
+    freeze {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = ids::<T>(0);
+    }: {
+        ContractsUniques::<T>::account_of(&caller)?;
+        FreezeReasons::<T>::insert(collection, item, u32::MAX);
+    }
+
+    thaw {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = ids::<T>(0);
+        FreezeReasons::<T>::insert(collection, item, u32::MAX);
+    }: {
+        ContractsUniques::<T>::account_of(&caller)?;
+        FreezeReasons::<T>::remove(collection, item);
+    }
+
+    freeze_status {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        FreezeReasons::<T>::insert(collection, item, u32::MAX);
+    }: {
+        ContractsUniques::<T>::freeze_status(&collection, &item);
+    }
+
+    act_for {
+        let caller: T::AccountId = whitelisted_caller();
+        ActingFor::<T>::insert(&caller, account::<T::AccountId>("real", 0, 0));
+    }: {
+        ContractsUniques::<T>::act_for(&caller, None)?;
+    }
+    verify {
+        assert!(!ActingFor::<T>::contains_key(&caller));
+    }
+
+    acting_for {
+        let caller: T::AccountId = whitelisted_caller();
+        ActingFor::<T>::insert(&caller, account::<T::AccountId>("real", 0, 0));
+    }: {
+        ActingFor::<T>::get(&caller);
+    }
+
+    admin_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        AdminConfirmers::<T>::insert(collection, account::<T::AccountId>("confirmer", 0, 0));
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::propose_admin_op(&owner, &collection, AdminOp::SetConfirmer(None))?;
+    }
+    verify {
+        assert!(PendingAdminOps::<T>::contains_key(collection));
+    }
+
+    confirm_admin_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let confirmer: T::AccountId = account("confirmer", 0, 0);
+        let (collection, _) = T::Backend::create_item(&caller);
+        AdminConfirmers::<T>::insert(collection, &confirmer);
+        ContractsUniques::<T>::propose_admin_op(&caller, &collection, AdminOp::SetConfirmer(None))?;
+    }: {
+        let confirmer = ContractsUniques::<T>::account_of(&confirmer)?;
+        ContractsUniques::<T>::confirm_admin_op(&confirmer, &collection)?;
+    }
+    verify {
+        assert!(!AdminConfirmers::<T>::contains_key(collection));
+    }
+
+    cancel_admin_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let confirmer: T::AccountId = account("confirmer", 0, 0);
+        let (collection, _) = T::Backend::create_item(&caller);
+        AdminConfirmers::<T>::insert(collection, &confirmer);
+        ContractsUniques::<T>::propose_admin_op(&caller, &collection, AdminOp::SetConfirmer(None))?;
+    }: {
+        let confirmer = ContractsUniques::<T>::account_of(&confirmer)?;
+        ContractsUniques::<T>::cancel_admin_op(&confirmer, &collection)?;
+    }
+    verify {
+        assert!(!PendingAdminOps::<T>::contains_key(collection));
+    }
+
+    pending_admin_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        AdminConfirmers::<T>::insert(collection, account::<T::AccountId>("confirmer", 0, 0));
+        ContractsUniques::<T>::propose_admin_op(&caller, &collection, AdminOp::SetConfirmer(None))?;
+    }: {
+        PendingAdminOps::<T>::get(collection);
+    }
+
+    admin_confirmer {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        AdminConfirmers::<T>::insert(collection, account::<T::AccountId>("confirmer", 0, 0));
+    }: {
+        AdminConfirmers::<T>::get(collection);
+    }
+
+    next_free_item_id {
+        let n in 1 .. T::MaxItemIdScan::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, mut item) = T::Backend::create_item(&caller);
+        NextItemIds::<T>::insert(collection, item);
+        for _ in 1..n {
+            item = item.increment().expect("benchmarked ids are far from the last one");
+            T::Backend::mint(&caller, &collection, &item, &caller)?;
+        }
+    }: {
+        ContractsUniques::<T>::next_free_item_id(&collection);
+    }
+    verify {
+        assert_eq!(NextItemIds::<T>::get(collection), item.increment());
+    }
+
+    mint_next {
+        let n in 1 .. T::MaxItemIdScan::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, mut item) = T::Backend::create_item(&caller);
+        NextItemIds::<T>::insert(collection, item);
+        for _ in 1..n {
+            item = item.increment().expect("benchmarked ids are far from the last one");
+            T::Backend::mint(&caller, &collection, &item, &caller)?;
+        }
+        let next = item.increment().expect("benchmarked ids are far from the last one");
+    }: {
+        ContractsUniques::<T>::mint_next(&caller, &collection, &caller)?;
+    }
+    verify {
+        assert_eq!(T::Backend::owner(&collection, &next), Some(caller));
+        assert_eq!(NextItemIds::<T>::get(collection), next.increment());
+    }
+
+    collections_owned {
+        let n in 0 .. T::MaxPageLen::get();
+        let caller: T::AccountId = whitelisted_caller();
+        for i in 0..n {
+            T::Backend::create_collection(&caller, i as u16);
+        }
+    }: {
+        T::Backend::collections_owned(&caller, None, n);
+    }
+
+    grant_capability {
+        let n in 0 .. T::MaxCapabilities::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let grantee: T::AccountId = account("grantee", 0, 0);
+        let (collection, _) = ids::<T>(0);
+        let func_ids: Vec<u16> = (0..n as u16).collect();
+    }: {
+        let grantor = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::grant_capability(&grantor, &grantee, &collection, &func_ids)?;
+    }
+    verify {
+        assert_eq!(Capabilities::<T>::decode_len(&caller, &grantee).unwrap_or_default() as u32, n);
+    }
+
+    capabilities {
+        let caller: T::AccountId = whitelisted_caller();
+        let grantee: T::AccountId = account("grantee", 0, 0);
+        let (collection, _) = ids::<T>(0);
+        let func_ids: Vec<u16> = (0..T::MaxCapabilities::get() as u16).collect();
+        ContractsUniques::<T>::grant_capability(&caller, &grantee, &collection, &func_ids)?;
+    }: {
+        let key = Capabilities::<T>::hashed_key_for(&caller, &grantee);
+        Output::stored_or(&key, &Vec::<(CollectionIdOf<T>, u16)>::new());
+    }
+
+    // Baseline of `capabilities`, decoding the capabilities only to encode them again.
+    #[extra]
+    capabilities_decoded {
+        let caller: T::AccountId = whitelisted_caller();
+        let grantee: T::AccountId = account("grantee", 0, 0);
+        let (collection, _) = ids::<T>(0);
+        let func_ids: Vec<u16> = (0..T::MaxCapabilities::get() as u16).collect();
+        ContractsUniques::<T>::grant_capability(&caller, &grantee, &collection, &func_ids)?;
+    }: {
+        Capabilities::<T>::get(&caller, &grantee).encode();
+    }
+
+    last_error {
+        let caller: T::AccountId = whitelisted_caller();
+        let error = LastError { func_id: 0xdb20, error: DispatchError::BadOrigin };
+        LastErrors::<T>::insert(&caller, error);
+    }: {
+        LastErrors::<T>::get(&caller);
+    }
+
+    publish_snapshot_root {
+        let (collection, _) = ids::<T>(0);
+        let root = T::Hashing::hash_of(&collection);
+    }: {
+        SnapshotRoots::<T>::insert(collection, T::BlockNumber::from(1u32), root);
+    }
+
+    verify_snapshot_membership {
+        let n in 0 .. T::MaxProofLen::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = ids::<T>(0);
+        let block_number = T::BlockNumber::from(1u32);
+        let proof: Vec<T::Hash> = (0..n).map(|i| T::Hashing::hash_of(&i)).collect();
+        let leaf = ContractsUniques::<T>::snapshot_leaf(&collection, &block_number, &caller);
+        let root = proof
+            .iter()
+            .fold(leaf, |node, sibling| ContractsUniques::<T>::snapshot_node(&node, sibling));
+        SnapshotRoots::<T>::insert(collection, block_number, root);
+    }: {
+        assert!(ContractsUniques::<T>::verify_snapshot_membership(
+            &collection,
+            &block_number,
+            &caller,
+            &proof
+        ));
+    }
+
+    set_memo {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        let memo: BoundedVec<u8, T::MaxMemoLen> =
+            vec![0xff; T::MaxMemoLen::get() as usize].try_into().unwrap();
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        ContractsUniques::<T>::set_memo(&owner, &collection, &item, memo)?;
+    }
+    verify {
+        assert!(Memos::<T>::contains_key(collection, item));
+    }
+
+    memo {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        let memo: BoundedVec<u8, T::MaxMemoLen> =
+            vec![0xff; T::MaxMemoLen::get() as usize].try_into().unwrap();
+        ContractsUniques::<T>::set_memo(&caller, &collection, &item, memo)?;
+    }: {
+        let output = ContractsUniques::<T>::memo_output(&collection, &item);
+        assert_eq!(output.as_bytes()[0], 1);
+    }
+
+    // Baseline of `memo`, decoding the memo only to encode it again.
+    #[extra]
+    memo_decoded {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        let memo: BoundedVec<u8, T::MaxMemoLen> =
+            vec![0xff; T::MaxMemoLen::get() as usize].try_into().unwrap();
+        ContractsUniques::<T>::set_memo(&caller, &collection, &item, memo)?;
+    }: {
+        assert_eq!(ContractsUniques::<T>::memo(&collection, &item).encode()[0], 1);
+    }
+
+    set_transfer_cooldown {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+    }: {
+        let owner = ContractsUniques::<T>::account_of(&caller)?;
+        let cooldown = Some(T::BlockNumber::from(10u32));
+        ContractsUniques::<T>::set_transfer_cooldown(&owner, &collection, cooldown)?;
+    }
+    verify {
+        assert!(TransferCooldowns::<T>::contains_key(collection));
+    }
+
+    transfer_cooldown {
+        let (collection, _) = ids::<T>(0);
+        TransferCooldowns::<T>::insert(collection, T::BlockNumber::from(10u32));
+    }: {
+        TransferCooldowns::<T>::get(collection);
+    }
+
+    free_holding {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+    }: {
+        T::Backend::free_holding(&collection);
+    }
+
+    submit_deferred_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+    }: {
+        ContractsUniques::<T>::submit_deferred_op(&caller, &collection, DeferredOp::Destroy)?;
+    }
+    verify {
+        assert_eq!(DeferredQueue::<T>::get().len(), 1);
+    }
+
+    // Cancelling a destruction also drops the mark of the collection.
+    cancel_deferred_op {
+        let n in 1 .. T::MaxDeferredOps::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        for _ in 1..n {
+            let op = DeferredOp::MintBatch { owner: caller.clone(), count: 1 };
+            ContractsUniques::<T>::submit_deferred_op(&caller, &collection, op)?;
+        }
+        ContractsUniques::<T>::submit_deferred_op(&caller, &collection, DeferredOp::Destroy)?;
+    }: {
+        ContractsUniques::<T>::cancel_deferred_op(&caller, n - 1)?;
+    }
+    verify {
+        assert_eq!(DeferredQueue::<T>::get().len() as u32, n - 1);
+        assert!(!Destructions::<T>::contains_key(collection));
+    }
+
+    deferred_op_status {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = ids::<T>(0);
+        let op = DeferredOp::MintBatch { owner: caller.clone(), count: 1 };
+        ContractsUniques::<T>::submit_deferred_op(&caller, &collection, op)?;
+    }: {
+        assert!(DeferredOps::<T>::get(0).is_some());
+    }
+
+    // A step minting to a collection that does not exist only costs the bookkeeping.
+    process_deferred_op {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = ids::<T>(0);
+        let op = DeferredOp::MintBatch { owner: caller.clone(), count: 2 };
+        ContractsUniques::<T>::submit_deferred_op(&caller, &collection, op)?;
+    }: {
+        ContractsUniques::<T>::process_deferred_ops(Weight::from_ref_time(u64::MAX));
+    }
+    verify {
+        assert!(DeferredQueue::<T>::get().is_empty());
+    }
+
+    // Benchmarks have no contract to register, so the steps of `set_watcher` are measured
+    // one by one.
+    set_watcher {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+    }: {
+        T::Backend::collection_owner(&collection);
+        Watchers::<T>::contains_key(collection);
+        StaleWatchedOwners::<T>::insert(collection, ());
+        pallet_contracts::Pallet::<T>::code_hash(&caller);
+        Watchers::<T>::insert(collection, (caller.clone(), caller.clone()));
+    }
+
+    watcher {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = ids::<T>(0);
+        Watchers::<T>::insert(collection, (caller.clone(), caller));
+    }: {
+        Watchers::<T>::get(collection);
+    }
+
+    // A step of the walk of `notify_watchers`, finding an item transferred since the
+    // previous visit.
+    scan_watched_item {
+        let caller: T::AccountId = whitelisted_caller();
+        let other: T::AccountId = account("other", 0, 0);
+        let (collection, item) = T::Backend::create_item(&caller);
+        Watchers::<T>::insert(collection, (caller.clone(), caller.clone()));
+        WatchedOwners::<T>::insert(collection, item, (0, other));
+    }: {
+        Watchers::<T>::get(collection);
+        StaleWatchedOwners::<T>::contains_key(collection);
+        let (_, owner) = T::Backend::items_of(&collection, None, 1).pop().expect("the item exists");
+        ContractsUniques::<T>::note_watched_owner(&collection, &item, &owner);
+    }
+    verify {
+        assert_eq!(WatchedOwners::<T>::get(collection, item), Some((0, caller)));
+    }
+
+    // Notifying an account without code only costs the lookups done before executing it.
+    notify_watcher {
+        let caller: T::AccountId = whitelisted_caller();
+    }: {
+        pallet_contracts::Pallet::<T>::bare_call(
+            caller.clone(),
+            caller.clone(),
+            Default::default(),
+            T::WatcherGasLimit::get(),
+            None,
+            ON_TRANSFER_SELECTOR.to_vec(),
+            false,
+        );
+    }
+
+    burn {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        FreezeReasons::<T>::insert(collection, item, 1);
+        LastTransfers::<T>::insert(collection, item, T::BlockNumber::from(0u32));
+        Provenance::<T>::insert(collection, item, T::Hash::default());
+    }: {
+        T::Backend::owner(&collection, &item);
+        ContractsUniques::<T>::forget_burned(&caller, &collection, &item);
+    }
+    verify {
+        assert!(!FreezeReasons::<T>::contains_key(collection, item));
+    }
+
+    allowance {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let (collection, item) = T::Backend::create_item(&caller);
+        T::Backend::approve_transfer(&caller, &collection, &item, &operator)?;
+    }: {
+        assert!(ContractsUniques::<T>::allowance(&collection, &caller, &operator, Some(&item)));
+    }
+
+    anchor_provenance {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+    }: {
+        Provenance::<T>::insert(collection, item, T::Hash::default());
+    }
+
+    verify_provenance {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, item) = T::Backend::create_item(&caller);
+        Provenance::<T>::insert(collection, item, T::Hash::default());
+    }: {
+        assert!(ContractsUniques::<T>::verify_provenance(&collection, &item, &T::Hash::default()));
+    }
+
+    bump_nonce {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        Nonces::<T>::insert(&caller, collection, 1);
+    }: {
+        ContractsUniques::<T>::bump_nonce(&caller, &collection);
+    }
+    verify {
+        assert_eq!(Nonces::<T>::get(&caller, collection), 2);
+    }
+
+    collection_nonce {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        Nonces::<T>::insert(&caller, collection, 1);
+    }: {
+        Nonces::<T>::get(&caller, collection);
+    }
+
+    // A destroyed collection created again is the case reading both the mark and the
+    // collection.
+    collection_destroyed {
+        let caller: T::AccountId = whitelisted_caller();
+        let (collection, _) = T::Backend::create_item(&caller);
+        Destructions::<T>::insert(collection, Destruction::Done);
+    }: {
+        assert!(!ContractsUniques::<T>::is_destroyed(&collection));
+    }
+}
+
+// The mock runtime only exists with the `uniques` backend.
+#[cfg(all(test, feature = "uniques"))]
+frame_benchmarking::impl_benchmark_test_suite!(
+    ContractsUniques,
+    crate::mock::new_test_ext(),
+    crate::mock::Test
+);
@@ -0,0 +1,61 @@
+//! Capabilities contracts grant each other.
+//!
+//! A capability lets a grantee call specific function IDs on a specific collection as
+//! its grantor, after selecting the grantor through `act_for`. Unlike handing over the
+//! collection's roles in the NFT pallet or making the grantee a proxy, the grantee gains
+//! nothing beyond the listed calls, and the grantor can withdraw them at any time.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Replaces the function IDs `grantor` lets `grantee` call on `collection`.
+    ///
+    /// Granting no function IDs withdraws the capability for `collection`.
+    pub(crate) fn grant_capability(
+        grantor: &T::AccountId,
+        grantee: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        func_ids: &[u16],
+    ) -> DispatchResult {
+        Capabilities::<T>::try_mutate_exists(grantor, grantee, |maybe_granted| {
+            let mut granted = maybe_granted.take().unwrap_or_default();
+            granted.retain(|(granted_collection, _)| granted_collection != collection);
+            for func_id in func_ids {
+                if !granted.contains(&(*collection, *func_id)) {
+                    granted
+                        .try_push((*collection, *func_id))
+                        .map_err(|_| Error::<T>::TooManyCapabilities)?;
+                }
+            }
+            if !granted.is_empty() {
+                *maybe_granted = Some(granted);
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns whether `grantor` lets `grantee` call `func_id` on `collection`.
+    pub fn has_capability(
+        grantor: &T::AccountId,
+        grantee: &T::AccountId,
+        func_id: u16,
+        collection: &CollectionIdOf<T>,
+    ) -> bool {
+        Capabilities::<T>::get(grantor, grantee).contains(&(*collection, func_id))
+    }
+
+    /// Returns the account `contract` operates as when calling `func_id` on `collection`.
+    ///
+    /// Unlike [`Pallet::account_of`], a capability granted by the user the contract acts
+    /// for is enough.
+    pub fn scoped_account_of(
+        contract: &T::AccountId,
+        func_id: u16,
+        collection: &CollectionIdOf<T>,
+    ) -> Result<T::AccountId, DispatchError> {
+        match ActingFor::<T>::get(contract) {
+            Some(real) if Self::has_capability(&real, contract, func_id, collection) => Ok(real),
+            _ => Self::account_of(contract),
+        }
+    }
+}
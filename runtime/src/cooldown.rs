@@ -0,0 +1,43 @@
+//! Minimum number of blocks between transfers of the same item.
+//!
+//! Trading an item back and forth inflates the volume marketplaces report. The owner of a
+//! collection can set a cooldown, after which every item transferred through the
+//! extension has to stay with its new owner for that many blocks. Transfers through the
+//! NFT pallet's extrinsics are neither limited nor recorded.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Sets the transfer cooldown of `collection`, or removes it if `cooldown` is `None`.
+    ///
+    /// Only the collection owner can set the cooldown.
+    pub(crate) fn set_transfer_cooldown(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        cooldown: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+        ensure!(
+            T::Backend::collection_owner(collection).as_ref() == Some(who),
+            Error::<T>::NotCollectionOwner
+        );
+        TransferCooldowns::<T>::set(collection, cooldown);
+        Ok(())
+    }
+
+    /// Returns whether the cooldown of `item` since its last transfer is still running.
+    pub(crate) fn is_cooling_down(collection: &CollectionIdOf<T>, item: &ItemIdOf<T>) -> bool {
+        match (TransferCooldowns::<T>::get(collection), LastTransfers::<T>::get(collection, item)) {
+            (Some(cooldown), Some(last)) =>
+                frame_system::Pallet::<T>::block_number() < last.saturating_add(cooldown),
+            _ => false,
+        }
+    }
+
+    /// Records that `item` was transferred, if its collection has a cooldown.
+    pub(crate) fn note_transfer(collection: &CollectionIdOf<T>, item: &ItemIdOf<T>) {
+        if TransferCooldowns::<T>::contains_key(collection) {
+            let now = frame_system::Pallet::<T>::block_number();
+            LastTransfers::<T>::insert(collection, item, now);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Function IDs runtimes serve on top of the built-in ones.
+//!
+//! Runtimes can add calls of their own, e.g. to a marketplace pallet, under the ID of the
+//! PSP-02 extension instead of registering another chain extension. Calls to a function
+//! ID in [`CUSTOM_FUNC_IDS`] are handed to the `CustomHandler` of the extension's
+//! `Config`; built-in function IDs never fall into that range, so custom function IDs do
+//! not collide with the ones future versions add.
+//!
+//! Handlers get the environment of the call as is, and charge weight, read the input and
+//! write the output themselves. [`crate::Pallet::scoped_account_of`] resolves the account
+//! the contract operates as, so `act_for` and capabilities cover custom function IDs too.
+
+use core::ops::RangeInclusive;
+
+use pallet_contracts::chain_extension::{
+    Environment,
+    Ext,
+    InitState,
+    RetVal,
+    SysConfig,
+    UncheckedFrom,
+};
+use sp_runtime::DispatchError;
+
+use crate::Config;
+
+/// The function IDs reserved for [`CustomHandler`]s. Built-in function IDs are never
+/// assigned from this range.
+pub const CUSTOM_FUNC_IDS: RangeInclusive<u16> = 0xf000..=0xffff;
+
+/// Serves the function IDs a runtime adds to the extension.
+pub trait CustomHandler<T: Config> {
+    /// Serves a call to `func_id`, one of the [`CUSTOM_FUNC_IDS`].
+    ///
+    /// `func_id` is the function ID the call is served under, following `LegacyFuncIds`.
+    /// Fails with `"Unimplemented func_id"` if the handler does not serve `func_id`.
+    fn call<E>(func_id: u16, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+    where
+        E: Ext<T = T>,
+        <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>;
+}
+
+/// Serves no custom function IDs.
+impl<T: Config> CustomHandler<T> for () {
+    fn call<E>(_func_id: u16, _env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+    where
+        E: Ext<T = T>,
+        <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    {
+        Err(DispatchError::Other("Unimplemented func_id"))
+    }
+}
@@ -0,0 +1,155 @@
+//! Tests of builds with the `debug` feature, where failed calls report a status code
+//! instead of trapping the contract and dry runs note every call for replays.
+
+use codec::Encode;
+use frame_support::{
+    assert_ok,
+    traits::Hooks,
+};
+use sp_runtime::DispatchError;
+
+use crate::{
+    mock::*,
+    status::{
+        FAILED,
+        TRANSFER_TO_OWNER,
+    },
+    LastError,
+};
+
+const COLLECTION: u32 = 0;
+const ITEM: u32 = 42;
+
+/// Deploys the fixture and mints items `0..5` of a collection owned by the fixture to it.
+fn setup() -> AccountId {
+    let contract = deploy_fixture();
+    assert_ok!(Uniques::force_create(RuntimeOrigin::root(), COLLECTION, contract.clone(), false));
+    for item in 0..5 {
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            item,
+            contract.clone()
+        ));
+    }
+    contract
+}
+
+#[test]
+fn last_error_returns_original_error() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_eq!(call_extension(&contract, 0x1ae3, ()).decode::<Option<LastError>>(), None);
+
+        let call = call_extension(&contract, 0xd123, COLLECTION);
+        assert_eq!(call.result, Ok((FAILED, vec![])));
+
+        let call = call_extension(&contract, 0x1ae3, ());
+        assert_eq!(
+            call.decode::<Option<LastError>>(),
+            Some(LastError {
+                func_id: 0xd123,
+                error: crate::Error::<Test>::NoPendingAdminOp.into(),
+            })
+        );
+    });
+}
+
+#[test]
+fn failed_calls_are_reverted() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        for item in 0..4 {
+            assert!(call_extension(&contract, 0x1932, (COLLECTION, item, BOB)).result.is_ok());
+        }
+
+        // The backend approves the item before the extension runs out of approvals.
+        let call = call_extension(&contract, 0x1932, (COLLECTION, 4, BOB));
+        assert_eq!(call.result, Ok((FAILED, vec![])));
+        assert!(Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, 4, BOB).is_err());
+        assert!(Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, 3, BOB).is_ok());
+    });
+}
+
+#[test]
+fn last_errors_are_cleared_every_block() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let call = call_extension(&contract, 0xffff, ());
+        assert_eq!(call.result, Ok((FAILED, vec![])));
+        assert_eq!(
+            call_extension(&contract, 0x1ae3, ()).decode::<Option<LastError>>(),
+            Some(LastError { func_id: 0xffff, error: DispatchError::Other("") })
+        );
+
+        ContractsUniques::on_initialize(2);
+        assert_eq!(crate::LastErrors::<Test>::get(&contract), None);
+        assert!(call_extension(&contract, 0x162d, (COLLECTION, ITEM)).result.is_ok());
+    });
+}
+
+#[test]
+fn dry_runs_note_calls_for_replay() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let input = (COLLECTION, 1u32).encode();
+        assert_eq!(
+            dry_run(&contract, 0x162d, &input),
+            [
+                note("call", &[&[0x16, 0x2d], &input]),
+                note("output", &[&Some(contract.clone()).encode()]),
+                note("status", &[&0u32.to_be_bytes()]),
+            ]
+            .concat()
+        );
+
+        let input = (COLLECTION, 1u32, &contract).encode();
+        assert_eq!(
+            dry_run(&contract, 0xdb20, &input),
+            [
+                note("call", &[&[0xdb, 0x20], &input]),
+                note("status", &[&TRANSFER_TO_OWNER.to_be_bytes()]),
+            ]
+            .concat()
+        );
+
+        // Failed calls are noted, too, although their changes are reverted.
+        let input = COLLECTION.encode();
+        assert_eq!(
+            dry_run(&contract, 0xd123, &input),
+            [note("call", &[&[0xd1, 0x23], &input]), note("status", &[&FAILED.to_be_bytes()])]
+                .concat()
+        );
+    });
+}
+
+#[test]
+fn calls_outside_dry_runs_are_not_noted() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let mut data = 0x162du32.encode();
+        data.extend((COLLECTION, 1u32).encode());
+        let result = Contracts::bare_call(ALICE, contract, 0, GAS_LIMIT, None, data, false);
+
+        assert!(result.result.is_ok());
+        assert!(result.debug_message.is_empty());
+    });
+}
+
+/// Calls `func_id` of the chain extension from `contract` with `input` as is in a dry run
+/// with debug output enabled, returning the debug message.
+fn dry_run(contract: &AccountId, func_id: u16, input: &[u8]) -> String {
+    let mut data = (func_id as u32).encode();
+    data.extend_from_slice(input);
+    let result = Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, data, true);
+    String::from_utf8(result.debug_message).unwrap()
+}
+
+/// Returns the line noting `parts` under `label` in the debug buffer.
+fn note(label: &str, parts: &[&[u8]]) -> String {
+    let parts: String = parts
+        .iter()
+        .map(|part| format!(" 0x{}", part.iter().map(|b| format!("{:02x}", b)).collect::<String>()))
+        .collect();
+    format!("psp02 {}:{}\n", label, parts)
+}
@@ -0,0 +1,142 @@
+//! Heavy operations executed across blocks.
+//!
+//! Minting many items or destroying a large collection can exceed what a single contract
+//! call may weigh. Contracts can instead queue such an operation, which is executed step
+//! by step in `on_idle`, with whatever weight blocks leave unused. Operations take turns
+//! step by step, so a large batch cannot starve the ones queued after it.
+//!
+//! An operation whose step fails, e.g. because the submitter is no longer the issuer of
+//! the collection, is dropped along with its remaining steps. So are the batches minting
+//! to a collection that was queued for destruction after them.
+
+use frame_support::storage::with_storage_layer;
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Queues `op` on `collection` on behalf of `who` and returns its id.
+    pub(crate) fn submit_deferred_op(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        op: DeferredOpOf<T>,
+    ) -> Result<u32, DispatchError> {
+        ensure!(!Self::is_destroyed(collection), Error::<T>::CollectionDestroyed);
+        if let DeferredOp::MintBatch { count, .. } = op {
+            ensure!(count > 0, Error::<T>::InvalidCommand);
+        }
+        if op == DeferredOp::Destroy {
+            ensure!(
+                T::Backend::collection_owner(collection).as_ref() == Some(who),
+                Error::<T>::NotCollectionOwner
+            );
+            ensure!(
+                !AdminConfirmers::<T>::contains_key(collection),
+                Error::<T>::ConfirmerRequired
+            );
+        }
+        let id = NextDeferredOpId::<T>::get();
+        DeferredQueue::<T>::try_append(id).map_err(|_| Error::<T>::DeferredQueueFull)?;
+        NextDeferredOpId::<T>::put(id.wrapping_add(1));
+        if op == DeferredOp::Destroy {
+            Self::note_destruction_queued(collection);
+        }
+        DeferredOps::<T>::insert(
+            id,
+            QueuedOp { op, collection: *collection, submitter: who.clone() },
+        );
+        Ok(id)
+    }
+
+    /// Drops the deferred operation `id` along with its remaining steps; `who` must be its
+    /// submitter.
+    pub(crate) fn cancel_deferred_op(who: &T::AccountId, id: u32) -> DispatchResult {
+        let queued = DeferredOps::<T>::get(id).ok_or(Error::<T>::UnknownDeferredOp)?;
+        ensure!(&queued.submitter == who, Error::<T>::NotSubmitter);
+        DeferredOps::<T>::remove(id);
+        DeferredQueue::<T>::mutate(|queue| queue.retain(|queued| *queued != id));
+        if queued.op == DeferredOp::Destroy {
+            Self::note_destruction_dropped(&queued.collection);
+        }
+        Ok(())
+    }
+
+    /// Executes the next steps of queued operations in turn for as long as they fit into
+    /// `limit`, and returns the weight used.
+    pub(crate) fn process_deferred_ops(limit: Weight) -> Weight {
+        let mut used = T::DbWeight::get().reads_writes(1, 1);
+        if used.ref_time() > limit.ref_time() {
+            return Weight::zero()
+        }
+        let mut queue = DeferredQueue::<T>::get();
+        while let Some(&id) = queue.first() {
+            let queued = match DeferredOps::<T>::get(id) {
+                Some(queued) => queued,
+                None => {
+                    queue.remove(0);
+                    continue
+                },
+            };
+            let step = Self::deferred_step_weight(&queued)
+                .saturating_add(T::WeightInfo::process_deferred_op());
+            if used.saturating_add(step).ref_time() > limit.ref_time() {
+                break
+            }
+            used = used.saturating_add(step);
+
+            queue.remove(0);
+            match Self::execute_deferred_step(queued) {
+                Some(rest) => {
+                    DeferredOps::<T>::insert(id, rest);
+                    // Cannot fail, as the id was just taken out of the queue.
+                    let _ = queue.try_push(id);
+                },
+                None => DeferredOps::<T>::remove(id),
+            }
+        }
+        DeferredQueue::<T>::put(queue);
+        used
+    }
+
+    /// Returns the weight of the next step of `queued`.
+    fn deferred_step_weight(queued: &QueuedOpOf<T>) -> Weight {
+        match queued.op {
+            DeferredOp::MintBatch { .. } => T::Backend::mint_weight()
+                .saturating_add(T::WeightInfo::mint_next(T::MaxItemIdScan::get()))
+                .saturating_add(T::WeightInfo::collection_destroyed()),
+            DeferredOp::Destroy => Self::admin_op_weight(&queued.collection, &AdminOp::Destroy),
+        }
+    }
+
+    /// Executes the next step of `queued`, and returns what is left of it unless it is
+    /// done or failed.
+    fn execute_deferred_step(queued: QueuedOpOf<T>) -> Option<QueuedOpOf<T>> {
+        let QueuedOp { op, collection, submitter } = queued;
+        match op {
+            DeferredOp::MintBatch { owner, count } => {
+                if Self::is_destroyed(&collection) {
+                    return None
+                }
+                let minted = with_storage_layer(|| {
+                    Self::mint_next(&submitter, &collection, &owner).map(|_| ())
+                });
+                (minted.is_ok() && count > 1).then_some(QueuedOp {
+                    op: DeferredOp::MintBatch { owner, count: count - 1 },
+                    collection,
+                    submitter,
+                })
+            },
+            DeferredOp::Destroy => {
+                // A confirmer may have been set since the operation was queued.
+                let destroyed = !AdminConfirmers::<T>::contains_key(collection) &&
+                    with_storage_layer(|| {
+                        Self::execute_admin_op(&submitter, &collection, AdminOp::Destroy)
+                    })
+                    .is_ok();
+                if !destroyed {
+                    Self::note_destruction_dropped(&collection);
+                }
+                None
+            },
+        }
+    }
+}
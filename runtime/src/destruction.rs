@@ -0,0 +1,44 @@
+//! Calls racing with the destruction of a collection.
+//!
+//! A collection queued for destruction through `submit_deferred_op` lives on until the
+//! step destroying it runs in `on_idle`, so items transferred, approved or minted in the
+//! meantime are about to disappear. Once the collection is gone, the NFT pallet only
+//! reports a generic failure. The pallet therefore tracks the collections destroyed or
+//! queued for destruction through the extension, and calls on their items fail with the
+//! `CollectionDestroyed` status code. A collection created again under the id of a
+//! destroyed one can be used again.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns whether `collection` is queued for destruction, or was destroyed through
+    /// the extension and not created again since.
+    pub(crate) fn is_destroyed(collection: &CollectionIdOf<T>) -> bool {
+        match Destructions::<T>::get(collection) {
+            Some(Destruction::Queued) => true,
+            Some(Destruction::Done) => T::Backend::collection_owner(collection).is_none(),
+            None => false,
+        }
+    }
+
+    /// Records that `collection` waits in the deferred queue to be destroyed.
+    pub(crate) fn note_destruction_queued(collection: &CollectionIdOf<T>) {
+        Destructions::<T>::insert(collection, Destruction::Queued);
+    }
+
+    /// Forgets that `collection` was queued for destruction, after the operation was
+    /// cancelled or failed.
+    pub(crate) fn note_destruction_dropped(collection: &CollectionIdOf<T>) {
+        Destructions::<T>::mutate_exists(collection, |destruction| {
+            if *destruction == Some(Destruction::Queued) {
+                *destruction = None;
+            }
+        });
+    }
+
+    /// Records that `collection` was destroyed.
+    pub(crate) fn note_destroyed(collection: &CollectionIdOf<T>) {
+        Destructions::<T>::insert(collection, Destruction::Done);
+        Self::forget_watcher(collection);
+    }
+}
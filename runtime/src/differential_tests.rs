@@ -0,0 +1,333 @@
+//! Runs the same scenarios against the `Uniques` and `Nfts` backends and checks that
+//! contracts observe identical results, so they can stay backend-agnostic.
+//!
+//! Only contract-visible results are compared: status codes, outputs and errors. Gas
+//! differs between backends since it depends on the weights of the NFT pallet.
+//! `free_holding` is left out, as only `pallet_uniques` can tell.
+
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_support::assert_ok;
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+use crate::{
+    mock,
+    mock::{
+        AccountId,
+        BOB,
+        CHARLIE,
+    },
+    mock_nfts,
+    AdminOp,
+    OwnershipAttestation,
+};
+
+const COLLECTION: u32 = 0;
+const ITEM: u32 = 42;
+
+/// The runtime types are the same for both backends.
+type Attestation = OwnershipAttestation<u64, u32, u32, AccountId>;
+type Op = AdminOp<u32, AccountId>;
+
+/// What a contract observes from a single chain extension call.
+type Outcome = Result<(u32, Vec<u8>), DispatchError>;
+
+/// A test runtime using one of the backends.
+trait Runtime {
+    fn execute_with<R>(f: impl FnOnce() -> R) -> R;
+    fn deploy_fixture_with_salt(salt: Vec<u8>) -> AccountId;
+    /// Creates `collection`, owned and administered by `owner`, and mints `item` to `owner`.
+    fn create_item(owner: &AccountId, collection: u32, item: u32);
+    fn set_block_number(n: u64);
+    fn call(contract: &AccountId, func_id: u16, input: impl Encode) -> Outcome;
+}
+
+struct Uniques;
+
+impl Runtime for Uniques {
+    fn execute_with<R>(f: impl FnOnce() -> R) -> R {
+        mock::new_test_ext().execute_with(f)
+    }
+
+    fn deploy_fixture_with_salt(salt: Vec<u8>) -> AccountId {
+        mock::deploy_fixture_with_salt(salt)
+    }
+
+    fn create_item(owner: &AccountId, collection: u32, item: u32) {
+        use mock::{
+            RuntimeOrigin,
+            Uniques,
+        };
+        let origin = RuntimeOrigin::signed(owner.clone());
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), collection, owner.clone(), false));
+        assert_ok!(Uniques::mint(origin, collection, item, owner.clone()));
+    }
+
+    fn set_block_number(n: u64) {
+        mock::System::set_block_number(n)
+    }
+
+    fn call(contract: &AccountId, func_id: u16, input: impl Encode) -> Outcome {
+        mock::call_extension(contract, func_id, input).result
+    }
+}
+
+struct Nfts;
+
+impl Runtime for Nfts {
+    fn execute_with<R>(f: impl FnOnce() -> R) -> R {
+        mock_nfts::new_test_ext().execute_with(f)
+    }
+
+    fn deploy_fixture_with_salt(salt: Vec<u8>) -> AccountId {
+        mock_nfts::deploy_fixture_with_salt(salt)
+    }
+
+    fn create_item(owner: &AccountId, collection: u32, item: u32) {
+        use mock_nfts::{
+            Nfts,
+            RuntimeOrigin,
+        };
+        let origin = RuntimeOrigin::signed(owner.clone());
+        let config = Default::default();
+        assert_ok!(Nfts::force_create(RuntimeOrigin::root(), collection, owner.clone(), config));
+        assert_ok!(Nfts::mint(origin, collection, item, owner.clone()));
+    }
+
+    fn set_block_number(n: u64) {
+        mock_nfts::System::set_block_number(n)
+    }
+
+    fn call(contract: &AccountId, func_id: u16, input: impl Encode) -> Outcome {
+        mock_nfts::call_extension(contract, func_id, input).result
+    }
+}
+
+/// Defines a test running `$scenario` on both backends and comparing what it recorded.
+macro_rules! differential_test {
+    ($name:ident, $scenario:ident) => {
+        #[test]
+        fn $name() {
+            let uniques = Uniques::execute_with($scenario::<Uniques>);
+            let nfts = Nfts::execute_with($scenario::<Nfts>);
+            assert_eq!(uniques, nfts);
+        }
+    };
+}
+
+/// Deploys the fixture and mints `ITEM` of a collection owned by the fixture to it.
+fn setup<R: Runtime>() -> AccountId {
+    let contract = R::deploy_fixture_with_salt(vec![]);
+    R::create_item(&contract, COLLECTION, ITEM);
+    contract
+}
+
+fn owner_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0x162d, (COLLECTION, ITEM)),
+        R::call(&contract, 0x162d, (COLLECTION, ITEM + 1)),
+        R::call(&contract, 0x162d, (COLLECTION + 1, ITEM)),
+    ]
+}
+differential_test!(owner_is_backend_agnostic, owner_scenario);
+
+fn transfer_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM + 1, BOB)),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x162d, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(transfer_is_backend_agnostic, transfer_scenario);
+
+fn transfer_destination_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, AccountId::new([0; 32]))),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, &contract)),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM + 1, &contract)),
+    ]
+}
+differential_test!(transfer_destination_checks_are_backend_agnostic, transfer_destination_scenario);
+
+fn approval_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    R::create_item(&contract, COLLECTION + 1, ITEM);
+    vec![
+        R::call(&contract, 0x9c69, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x1932, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x1932, (COLLECTION + 1, ITEM, BOB)),
+        R::call(&contract, 0x817c, BOB),
+        R::call(&contract, 0x9c69, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x817c, BOB),
+        R::call(&contract, 0x2612, BOB),
+        R::call(&contract, 0x817c, BOB),
+        R::call(&contract, 0x1932, (COLLECTION, ITEM + 1, BOB)),
+    ]
+}
+differential_test!(approvals_are_backend_agnostic, approval_scenario);
+
+fn metadata_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0x3e09, (COLLECTION, ITEM)),
+        R::call(&contract, 0xb5e2, (COLLECTION, ITEM, b"ipfs://pet".to_vec())),
+        R::call(&contract, 0xb5e2, (COLLECTION, ITEM, vec![0u8; 51])),
+        R::call(&contract, 0x3e09, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(metadata_is_backend_agnostic, metadata_scenario);
+
+fn freeze_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0x4cb3, (COLLECTION, ITEM)),
+        R::call(&contract, 0xce8e, (COLLECTION, ITEM, Some(7u32))),
+        R::call(&contract, 0x4cb3, (COLLECTION, ITEM)),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0xa49a, (COLLECTION, ITEM)),
+        R::call(&contract, 0x4cb3, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(freezing_is_backend_agnostic, freeze_scenario);
+
+fn attestation_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let attestation = R::call(&contract, 0x145a, (COLLECTION, ITEM));
+    let output = attestation.clone().unwrap().1;
+    let (attested, _) = Option::<(Attestation, H256)>::decode(&mut &output[..]).unwrap().unwrap();
+    R::set_block_number(2);
+    let forged = Attestation { owner: BOB, ..attested.clone() };
+    vec![
+        attestation,
+        R::call(&contract, 0x145a, (COLLECTION, ITEM + 1)),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0xe8c5, &attested),
+        R::call(&contract, 0xe8c5, &forged),
+    ]
+}
+differential_test!(attestations_are_backend_agnostic, attestation_scenario);
+
+fn admin_op_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let confirmer = R::deploy_fixture_with_salt(vec![1]);
+    vec![
+        R::call(&contract, 0x5873, (COLLECTION, Op::SetConfirmer(Some(confirmer.clone())))),
+        R::call(&contract, 0x5873, (COLLECTION, Op::ForceTransfer { item: ITEM, dest: CHARLIE })),
+        R::call(&contract, 0xc29f, COLLECTION),
+        R::call(&contract, 0xe63e, COLLECTION),
+        R::call(&confirmer, 0xe63e, COLLECTION),
+        R::call(&contract, 0x162d, (COLLECTION, ITEM)),
+        R::call(&contract, 0x5873, (COLLECTION, Op::Destroy)),
+        R::call(&confirmer, 0xe63e, COLLECTION),
+        R::call(&contract, 0x162d, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(admin_ops_are_backend_agnostic, admin_op_scenario);
+
+fn next_free_item_id_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0x32ae, COLLECTION),
+        R::call(&contract, 0x32ae, COLLECTION + 1),
+    ]
+}
+differential_test!(next_free_item_id_is_backend_agnostic, next_free_item_id_scenario);
+
+fn mint_next_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![
+        R::call(&contract, 0xaeed, (COLLECTION, BOB)),
+        R::call(&contract, 0xaeed, (COLLECTION, CHARLIE)),
+        R::call(&contract, 0x162d, (COLLECTION, 1u32)),
+        R::call(&contract, 0xaeed, (COLLECTION + 1, BOB)),
+    ]
+}
+differential_test!(mint_next_is_backend_agnostic, mint_next_scenario);
+
+fn collections_owned_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    R::create_item(&BOB, COLLECTION + 1, ITEM);
+    vec![
+        R::call(&contract, 0xbb93, (&contract, None::<u32>, 2u32)),
+        R::call(&contract, 0xbb93, (BOB, None::<u32>, 2u32)),
+        R::call(&contract, 0xbb93, (CHARLIE, None::<u32>, 2u32)),
+    ]
+}
+differential_test!(collections_owned_is_backend_agnostic, collections_owned_scenario);
+
+fn capability_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let grantee = R::deploy_fixture_with_salt(vec![1]);
+    vec![
+        R::call(&contract, 0x253d, (&grantee, COLLECTION, vec![0xce8eu16])),
+        R::call(&grantee, 0x0d2d, Some(&contract)),
+        R::call(&grantee, 0xce8e, (COLLECTION, ITEM, Some(1u32))),
+        R::call(&grantee, 0xa49a, (COLLECTION, ITEM)),
+        R::call(&contract, 0x4cb3, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(capabilities_are_backend_agnostic, capability_scenario);
+
+fn memo_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let other = R::deploy_fixture_with_salt(vec![1]);
+    vec![
+        R::call(&contract, 0xc60a, (COLLECTION, ITEM, b"Rex".to_vec())),
+        R::call(&other, 0xc60a, (COLLECTION, ITEM, b"Max".to_vec())),
+        R::call(&contract, 0x05a6, (COLLECTION, ITEM)),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x05a6, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(memos_are_backend_agnostic, memo_scenario);
+
+fn transfer_cooldown_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let other = R::deploy_fixture_with_salt(vec![1]);
+    R::set_block_number(5);
+    vec![
+        R::call(&contract, 0x813e, (COLLECTION, Some(10u64))),
+        R::call(&contract, 0xdb20, (COLLECTION, ITEM, &other)),
+        R::call(&other, 0xdb20, (COLLECTION, ITEM, BOB)),
+        R::call(&contract, 0x1e27, COLLECTION),
+    ]
+}
+differential_test!(transfer_cooldown_is_backend_agnostic, transfer_cooldown_scenario);
+
+fn transfer_batch_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    R::create_item(&contract, COLLECTION + 1, ITEM);
+    vec![
+        R::call(&contract, 0x3df4, (COLLECTION, vec![(ITEM, BOB), (ITEM + 1, BOB)], false)),
+        R::call(&contract, 0x3df4, (COLLECTION, vec![(ITEM, BOB)], true)),
+        R::call(&contract, 0x3df4, (COLLECTION + 1, vec![(ITEM, CHARLIE)], false)),
+        R::call(&contract, 0x162d, (COLLECTION, ITEM)),
+    ]
+}
+differential_test!(transfer_batch_is_backend_agnostic, transfer_batch_scenario);
+
+fn allowance_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    let allowance = |operator: &AccountId, item: Option<u32>| {
+        R::call(&contract, 0x4790, (COLLECTION, &contract, operator, item))
+    };
+    let mut outcomes = vec![R::call(&contract, 0x1932, (COLLECTION, ITEM, BOB))];
+    outcomes.extend([allowance(&BOB, Some(ITEM)), allowance(&BOB, None)]);
+    outcomes.push(R::call(&contract, 0x253d, (CHARLIE, COLLECTION, vec![0xdb20u16])));
+    outcomes.extend([allowance(&CHARLIE, Some(ITEM + 1)), allowance(&CHARLIE, None)]);
+    outcomes
+}
+differential_test!(allowance_is_backend_agnostic, allowance_scenario);
+
+fn unknown_func_id_scenario<R: Runtime>() -> Vec<Outcome> {
+    let contract = setup::<R>();
+    vec![R::call(&contract, 0xffff, ())]
+}
+differential_test!(unknown_func_id_is_backend_agnostic, unknown_func_id_scenario);
+
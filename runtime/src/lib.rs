@@ -17,15 +17,78 @@
 //! Pallet to handle XCM messages.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+// The pallet has to build for `no_std` runtimes, so items available in `core` are taken
+// from there, and everything else from `sp_std`.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc, clippy::alloc_instead_of_core)]
 
+mod admin;
+mod allocator;
+mod capability;
+mod cooldown;
+mod deferred;
+mod destruction;
+mod memo;
+mod nonce;
+mod output;
+mod provenance;
+pub mod backend;
+pub mod custom;
+pub mod proxy;
 mod psp02_ext;
+mod snapshot;
+pub mod status;
+mod types;
+mod watcher;
+pub mod weights;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(all(test, feature = "uniques"))]
+mod mock;
+// Debug builds report failures through a status code, which the regular tests expect to trap.
+#[cfg(all(test, feature = "uniques", not(feature = "debug")))]
+mod tests;
+#[cfg(all(test, feature = "uniques", feature = "debug"))]
+mod debug_tests;
+#[cfg(all(test, feature = "uniques", feature = "nfts"))]
+mod mock_nfts;
+#[cfg(all(test, feature = "uniques", feature = "nfts"))]
+mod differential_tests;
+
+use frame_support::{
+	storage::with_storage_layer,
+	traits::Currency,
+};
+
+pub use backend::NftBackend;
+pub use custom::CustomHandler;
+pub use proxy::ProxyCheck;
 pub use psp02_ext::Psp02Extension;
 pub use pallet::*;
+pub use types::*;
+pub use watcher::ON_TRANSFER_SELECTOR;
+pub use weights::WeightInfo;
+
+/// Collection identifier of the configured backend.
+pub type CollectionIdOf<T> = <<T as Config>::Backend as NftBackend<
+	<T as frame_system::Config>::AccountId,
+>>::CollectionId;
+/// Item identifier of the configured backend.
+pub type ItemIdOf<T> =
+	<<T as Config>::Backend as NftBackend<<T as frame_system::Config>::AccountId>>::ItemId;
+/// Balance of the currency contracts pay their storage deposits in.
+pub type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Hash;
+	use sp_std::vec::Vec;
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -33,12 +96,577 @@ pub mod pallet {
 
 	#[pallet::config]
 	/// The pallet configuration trait.
-	pub trait Config: frame_system::Config + pallet_contracts::Config + pallet_uniques::Config {}
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The NFT pallet the extension operates on, e.g. `backend::Uniques` or
+		/// `backend::Nfts`.
+		type Backend: NftBackend<Self::AccountId>;
+
+		/// The maximum number of items an account can have approved to a single delegate
+		/// through the extension.
+		#[pallet::constant]
+		type MaxApprovals: Get<u32>;
+
+		/// The maximum number of delegates an account can have items approved to through
+		/// the extension, which bounds the approvals a transfer or burn looks at.
+		#[pallet::constant]
+		type MaxDelegates: Get<u32>;
+
+		/// Decides whether a contract may act for a user, e.g. `proxy::PalletProxy`.
+		/// Use `()` to only let contracts act for themselves.
+		type ProxyCheck: ProxyCheck<Self::AccountId>;
+
+		/// The number of blocks a confirmer has to confirm a pending admin operation.
+		#[pallet::constant]
+		type ConfirmationPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of item ids a single `next_free_item_id` call looks at.
+		#[pallet::constant]
+		type MaxItemIdScan: Get<u32>;
+
+		/// Whether contracts are kept from transferring items to themselves, which usually
+		/// leaves items stuck in contracts that cannot move them out again.
+		#[pallet::constant]
+		type ForbidTransfersToSelf: Get<bool>;
+
+		/// The maximum number of `(collection, function ID)` pairs an account can grant to
+		/// a single grantee.
+		#[pallet::constant]
+		type MaxCapabilities: Get<u32>;
+
+		/// The maximum number of entries a paginated query returns.
+		#[pallet::constant]
+		type MaxPageLen: Get<u32>;
+
+		/// The origin publishing ownership snapshots, e.g. `EnsureRoot` or a council.
+		type SnapshotOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of hashes in a proof of snapshot membership, i.e. the depth
+		/// of the deepest snapshot tree.
+		#[pallet::constant]
+		type MaxProofLen: Get<u32>;
+
+		/// The maximum length of the memo the owner of an item can attach to it.
+		#[pallet::constant]
+		type MaxMemoLen: Get<u32>;
+
+		/// The maximum number of transfers in a single `transfer_batch` call.
+		#[pallet::constant]
+		type MaxBatchLen: Get<u32>;
+
+		/// Function IDs served under another function ID, as `(legacy, current)` pairs, so
+		/// contracts built against a previous numbering keep working after a renumbering.
+		/// Use `()` if there is nothing to remap.
+		type LegacyFuncIds: Get<&'static [(u16, u16)]>;
+
+		/// Serves the function IDs in [`custom::CUSTOM_FUNC_IDS`] the runtime adds to the
+		/// extension. Use `()` to serve none.
+		type CustomHandler: CustomHandler<Self>;
+
+		/// The maximum number of operations waiting in the deferred queue. Use `0` to
+		/// disable the queue.
+		#[pallet::constant]
+		type MaxDeferredOps: Get<u32>;
+
+		/// The gas a watcher contract is given to handle the notification of a transfer.
+		#[pallet::constant]
+		type WatcherGasLimit: Get<Weight>;
+
+		/// The storage deposit a watcher contract may charge the account it is called from
+		/// for handling the notification of a transfer.
+		#[pallet::constant]
+		type WatcherDepositLimit: Get<BalanceOf<Self>>;
+
+		/// Weights of the work the extension does on top of the backend.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The user each contract currently acts for.
+	#[pallet::storage]
+	pub type ActingFor<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// The function IDs each grantor lets a grantee call per collection.
+	#[pallet::storage]
+	pub type Capabilities<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(CollectionIdOf<T>, u16), T::MaxCapabilities>,
+		ValueQuery,
+	>;
+
+	/// The account that has to confirm destructive operations on a collection.
+	#[pallet::storage]
+	pub type AdminConfirmers<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, T::AccountId>;
+
+	/// The admin operation waiting for confirmation on a collection.
+	#[pallet::storage]
+	pub type PendingAdminOps<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, PendingAdminOpOf<T>>;
+
+	/// The item id of each collection the search for an unused id starts from.
+	#[pallet::storage]
+	pub type NextItemIds<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, ItemIdOf<T>>;
+
+	/// Items approved through the extension, keyed by owner and delegate.
+	#[pallet::storage]
+	pub type Approvals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(CollectionIdOf<T>, ItemIdOf<T>), T::MaxApprovals>,
+		ValueQuery,
+	>;
+
+	/// The number of delegates each owner has items approved to in `Approvals`.
+	#[pallet::storage]
+	pub type DelegateCounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Commitments to the ownership attestations issued through the extension, mapped to
+	/// the block they were issued at.
+	#[pallet::storage]
+	pub type Attestations<T: Config> = StorageMap<_, Identity, T::Hash, T::BlockNumber>;
+
+	/// Reason codes recorded for items frozen through the extension.
+	#[pallet::storage]
+	pub type FreezeReasons<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Blake2_128Concat,
+		ItemIdOf<T>,
+		u32,
+	>;
+
+	/// The merkle roots of the ownership snapshots of each collection, keyed by the block
+	/// they were taken at.
+	#[pallet::storage]
+	pub type SnapshotRoots<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Twox64Concat,
+		T::BlockNumber,
+		T::Hash,
+	>;
+
+	/// The memo of each item along with the owner who wrote it.
+	#[pallet::storage]
+	pub type Memos<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Blake2_128Concat,
+		ItemIdOf<T>,
+		(T::AccountId, BoundedVec<u8, T::MaxMemoLen>),
+	>;
+
+	/// The number of mutating calls each contract made against each collection through the
+	/// extension.
+	#[pallet::storage]
+	pub type Nonces<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		u64,
+		ValueQuery,
+	>;
+
+	/// The content hash anchored to each item minted with one.
+	#[pallet::storage]
+	pub type Provenance<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Blake2_128Concat,
+		ItemIdOf<T>,
+		T::Hash,
+	>;
+
+	/// The minimum number of blocks between transfers of an item through the extension, set
+	/// by the collection owner.
+	#[pallet::storage]
+	pub type TransferCooldowns<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, T::BlockNumber>;
+
+	/// The block each item of a collection with a cooldown was last transferred at through
+	/// the extension.
+	#[pallet::storage]
+	pub type LastTransfers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Blake2_128Concat,
+		ItemIdOf<T>,
+		T::BlockNumber,
+	>;
+
+	/// The id of the next deferred operation.
+	#[pallet::storage]
+	pub type NextDeferredOpId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The deferred operations waiting to be executed.
+	#[pallet::storage]
+	pub type DeferredOps<T: Config> = StorageMap<_, Twox64Concat, u32, QueuedOpOf<T>>;
+
+	/// The ids of the deferred operations in the order their next steps are executed.
+	#[pallet::storage]
+	pub type DeferredQueue<T: Config> =
+		StorageValue<_, BoundedVec<u32, T::MaxDeferredOps>, ValueQuery>;
+
+	/// The collections destroyed or queued for destruction through the extension.
+	#[pallet::storage]
+	pub type Destructions<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, Destruction>;
+
+	/// The contract notified of the transfers of each collection made outside the
+	/// extension, along with the account that registered it.
+	#[pallet::storage]
+	pub type Watchers<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, (T::AccountId, T::AccountId)>;
+
+	/// The number of watchers registered for each collection so far, which tells the owners
+	/// seen for the current watcher from those seen for earlier ones.
+	#[pallet::storage]
+	pub type WatcherRegistrations<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, u32, ValueQuery>;
+
+	/// The owner of each item of a watched collection as last seen by the scan for transfers
+	/// made outside the extension, along with the registration it was seen for.
+	#[pallet::storage]
+	pub type WatchedOwners<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionIdOf<T>,
+		Blake2_128Concat,
+		ItemIdOf<T>,
+		(u32, T::AccountId),
+	>;
+
+	/// The collections whose `WatchedOwners` are removed in `on_idle`, as their watcher was
+	/// unregistered or replaced, or they were destroyed.
+	#[pallet::storage]
+	pub type StaleWatchedOwners<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionIdOf<T>, ()>;
+
+	/// The collection and the last of its items the scan for transfers made outside the
+	/// extension looked at, where the scan resumes in the next block.
+	#[pallet::storage]
+	pub type WatchCursor<T: Config> = StorageValue<_, (CollectionIdOf<T>, Option<ItemIdOf<T>>)>;
+
+	/// The last failed call of each contract to the extension. Only written by builds with
+	/// the `debug` feature, and cleared at the start of every block.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type LastErrors<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, LastError>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			if !cfg!(feature = "debug") {
+				return Weight::zero()
+			}
+			let removed = LastErrors::<T>::clear(u32::MAX, None);
+			T::DbWeight::get().reads_writes(removed.loops.into(), removed.unique.into())
+		}
+
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			// Watchers go first, so notifications lag behind transfers as little as possible.
+			let notified = Self::notify_watchers(remaining_weight);
+			let remaining_weight = remaining_weight.saturating_sub(notified);
+			let cleared = Self::clear_stale_watched_owners(remaining_weight);
+			let remaining_weight = remaining_weight.saturating_sub(cleared);
+			let processed = Self::process_deferred_ops(remaining_weight);
+			notified.saturating_add(cleared).saturating_add(processed)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Publishes the merkle root of the ownership snapshot of `collection` at
+		/// `block_number`, or withdraws the snapshot if `root` is `None`.
+		///
+		/// See the `snapshot` module for how the tree is built.
+		#[pallet::weight(T::WeightInfo::publish_snapshot_root())]
+		pub fn publish_snapshot_root(
+			origin: OriginFor<T>,
+			collection: CollectionIdOf<T>,
+			block_number: T::BlockNumber,
+			root: Option<T::Hash>,
+		) -> DispatchResult {
+			T::SnapshotOrigin::ensure_origin(origin)?;
+			SnapshotRoots::<T>::set(collection, block_number, root);
+			Ok(())
+		}
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		InvalidCommand
+		InvalidCommand,
+		/// The owner already approved `MaxApprovals` items to the delegate.
+		TooManyApprovals,
+		/// The contract is not a proxy of the user it tries to act for, nor holds a
+		/// capability of the user covering the call.
+		NotProxy,
+		/// Only the collection owner can propose admin operations.
+		NotCollectionOwner,
+		/// Only the collection's confirmer can confirm admin operations.
+		NotConfirmer,
+		/// The collection owner cannot confirm its own admin operations.
+		ConfirmerIsOwner,
+		/// Another admin operation is waiting for confirmation on the collection.
+		AdminOpPending,
+		/// No admin operation is waiting for confirmation on the collection.
+		NoPendingAdminOp,
+		/// The confirmation period of the admin operation is over.
+		AdminOpExpired,
+		/// No unused item id was found within `MaxItemIdScan` ids of the collection's
+		/// cursor, or the collection does not exist.
+		NoFreeItemId,
+		/// The grantor already granted `MaxCapabilities` capabilities to the grantee.
+		TooManyCapabilities,
+		/// Only the owner of an item can write its memo.
+		NotItemOwner,
+		/// The deferred queue holds `MaxDeferredOps` operations already.
+		DeferredQueueFull,
+		/// No deferred operation with the id is waiting in the queue.
+		UnknownDeferredOp,
+		/// Only the submitter of a deferred operation can cancel it.
+		NotSubmitter,
+		/// The collection has a confirmer, so it can only be destroyed through a confirmed
+		/// admin operation.
+		ConfirmerRequired,
+		/// Only contracts can be registered as watchers.
+		NotContract,
+		/// The collection was destroyed, or is queued for destruction.
+		CollectionDestroyed,
+		/// The owner already approved items to `MaxDelegates` delegates.
+		TooManyDelegates,
 	}
 
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Returns the account `contract` operates as: the user it acts for, or itself.
+		pub fn account_of(contract: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+			match ActingFor::<T>::get(contract) {
+				Some(real) => {
+					ensure!(T::ProxyCheck::is_proxy(&real, contract), Error::<T>::NotProxy);
+					Ok(real)
+				},
+				None => Ok(contract.clone()),
+			}
+		}
+
+		/// Makes `contract` act for `real`, or for itself again if `real` is `None`.
+		///
+		/// `contract` has to be a proxy of `real` or hold a capability granted by `real`.
+		pub(crate) fn act_for(contract: &T::AccountId, real: Option<T::AccountId>) -> DispatchResult {
+			if let Some(real) = &real {
+				ensure!(
+					T::ProxyCheck::is_proxy(real, contract) ||
+						Capabilities::<T>::contains_key(real, contract),
+					Error::<T>::NotProxy
+				);
+			}
+			ActingFor::<T>::set(contract, real);
+			Ok(())
+		}
+
+		/// Returns the items `owner` still owns and approved `delegate` to transfer.
+		pub fn approvals_to(
+			owner: &T::AccountId,
+			delegate: &T::AccountId,
+		) -> Vec<(CollectionIdOf<T>, ItemIdOf<T>)> {
+			Self::iter_approvals_to(owner, delegate).collect()
+		}
+
+		/// Iterates over the items `owner` still owns and approved `delegate` to transfer.
+		pub(crate) fn iter_approvals_to<'a>(
+			owner: &'a T::AccountId,
+			delegate: &T::AccountId,
+		) -> impl Iterator<Item = (CollectionIdOf<T>, ItemIdOf<T>)> + 'a {
+			Approvals::<T>::get(owner, delegate)
+				.into_iter()
+				.filter(move |(collection, item)| {
+					T::Backend::owner(collection, item).as_ref() == Some(owner)
+				})
+		}
+
+		/// Returns whether `operator` may transfer `item` of `owner`, or every item `owner`
+		/// holds in `collection` if `item` is `None`, following `allowance` of PSP34.
+		///
+		/// An item is approved through the NFT pallet. The whole collection is approved by
+		/// making `operator` a proxy of `owner` or granting it the capability to call
+		/// `transfer` on the collection. These are the checks `transfer` applies when
+		/// `operator` moves the items of `owner`.
+		pub fn allowance(
+			collection: &CollectionIdOf<T>,
+			owner: &T::AccountId,
+			operator: &T::AccountId,
+			item: Option<&ItemIdOf<T>>,
+		) -> bool {
+			T::ProxyCheck::is_proxy(owner, operator) ||
+				Self::has_capability(owner, operator, psp02_ext::TRANSFER, collection) ||
+				item.map_or(false, |item| {
+					T::Backend::owner(collection, item).as_ref() == Some(owner) &&
+						T::Backend::is_approved(collection, item, operator)
+				})
+		}
+
+		/// Attests the current owner of `item`, if it exists, and records the commitment to
+		/// the attestation.
+		pub fn attest_ownership(
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+		) -> Option<(OwnershipAttestationOf<T>, T::Hash)> {
+			let attestation = OwnershipAttestation {
+				block_number: frame_system::Pallet::<T>::block_number(),
+				collection: *collection,
+				item: *item,
+				owner: T::Backend::owner(collection, item)?,
+			};
+			let commitment = T::Hashing::hash_of(&attestation);
+			Attestations::<T>::insert(commitment, attestation.block_number);
+			Some((attestation, commitment))
+		}
+
+		/// Returns whether `attestation` was issued by [`Pallet::attest_ownership`].
+		pub fn verify_attestation(attestation: &OwnershipAttestationOf<T>) -> bool {
+			Attestations::<T>::get(T::Hashing::hash_of(attestation)) ==
+				Some(attestation.block_number)
+		}
+
+		/// Returns whether `item` is frozen and the reason code it was frozen with, if any.
+		///
+		/// Items frozen outside of the extension are reported without a reason.
+		pub fn freeze_status(
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+		) -> (bool, Option<u32>) {
+			if T::Backend::is_frozen(collection, item) {
+				(true, FreezeReasons::<T>::get(collection, item))
+			} else {
+				(false, None)
+			}
+		}
+
+		/// Cancels every approval `owner` granted to `delegate` through the extension.
+		///
+		/// Approvals of items that changed hands or were cancelled outside of the extension
+		/// are already gone, so only the items whose approval got cancelled are returned, in
+		/// the vector the approvals were stored in. If the NFT pallet fails to cancel one, its
+		/// error is returned and the approvals stay as they were, tracking included.
+		pub(crate) fn revoke_all_approvals(
+			owner: &T::AccountId,
+			delegate: &T::AccountId,
+		) -> Result<BoundedVec<(CollectionIdOf<T>, ItemIdOf<T>), T::MaxApprovals>, DispatchError> {
+			with_storage_layer(|| {
+				let mut items = Approvals::<T>::take(owner, delegate);
+				if !items.is_empty() {
+					DelegateCounts::<T>::mutate_exists(owner, |count| Self::decrement(count));
+				}
+				items.retain(|(collection, item)| {
+					T::Backend::owner(collection, item).as_ref() == Some(owner) &&
+						T::Backend::is_approved(collection, item, delegate)
+				});
+				for (collection, item) in &items {
+					T::Backend::cancel_approval(owner, collection, item, delegate)?;
+				}
+				Ok(items)
+			})
+		}
+
+		/// Records that `owner` approved `delegate` to transfer `item`.
+		///
+		/// Returns the number of delegates whose approvals had to be looked at.
+		pub(crate) fn note_approval(
+			owner: &T::AccountId,
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+			delegate: &T::AccountId,
+		) -> Result<u32, DispatchError> {
+			let delegates = if T::Backend::REPLACES_APPROVAL {
+				Self::forget_item(owner, collection, item)
+			} else {
+				0
+			};
+			Approvals::<T>::try_mutate(owner, delegate, |items| {
+				if items.is_empty() {
+					DelegateCounts::<T>::try_mutate(owner, |count| {
+						ensure!(*count < T::MaxDelegates::get(), Error::<T>::TooManyDelegates);
+						*count += 1;
+						Ok::<_, DispatchError>(())
+					})?;
+				}
+				if !items.contains(&(*collection, *item)) {
+					items
+						.try_push((*collection, *item))
+						.map_err(|_| Error::<T>::TooManyApprovals)?;
+				}
+				Ok::<_, DispatchError>(())
+			})?;
+			Ok(delegates)
+		}
+
+		/// Removes `item` from the items `owner` approved to `delegate`.
+		pub(crate) fn forget_approval(
+			owner: &T::AccountId,
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+			delegate: &T::AccountId,
+		) {
+			Approvals::<T>::mutate_exists(owner, delegate, |maybe_items| {
+				if let Some(items) = maybe_items {
+					items.retain(|approved| approved != &(*collection, *item));
+					if items.is_empty() {
+						*maybe_items = None;
+						DelegateCounts::<T>::mutate_exists(owner, |count| Self::decrement(count));
+					}
+				}
+			});
+		}
+
+		/// Removes `item` from the items `owner` approved to any delegate.
+		///
+		/// Returns the number of delegates `owner` approved items to, at most `MaxDelegates`.
+		pub(crate) fn forget_item(
+			owner: &T::AccountId,
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+		) -> u32 {
+			let delegates: Vec<_> = Approvals::<T>::iter_key_prefix(owner).collect();
+			for delegate in &delegates {
+				Self::forget_approval(owner, collection, item, delegate);
+			}
+			delegates.len() as u32
+		}
+
+		/// Removes what the extension recorded about the burned `item` of `owner`.
+		///
+		/// Returns the number of delegates `owner` approved items to.
+		pub(crate) fn forget_burned(
+			owner: &T::AccountId,
+			collection: &CollectionIdOf<T>,
+			item: &ItemIdOf<T>,
+		) -> u32 {
+			FreezeReasons::<T>::remove(collection, item);
+			Memos::<T>::remove(collection, item);
+			LastTransfers::<T>::remove(collection, item);
+			Provenance::<T>::remove(collection, item);
+			WatchedOwners::<T>::remove(collection, item);
+			Self::forget_item(owner, collection, item)
+		}
+
+		/// Decrements a count, removing it once it drops to zero.
+		fn decrement(count: &mut Option<u32>) {
+			*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+		}
+	}
 }
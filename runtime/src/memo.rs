@@ -0,0 +1,40 @@
+//! Short notes the owner of an item attaches to it, e.g. the name of a pet.
+//!
+//! Attributes of the NFT pallet belong to the collection's creator, so memos are kept in
+//! a namespace of the extension's own, [`pallet::Memos`], and never collide with them.
+//! A memo belongs to the owner who wrote it and is no longer returned once the item
+//! changed hands, however it was transferred.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Replaces the memo of `item` with `memo`, or removes it if `memo` is empty.
+    ///
+    /// Only the owner of the item can write its memo.
+    pub(crate) fn set_memo(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        item: &ItemIdOf<T>,
+        memo: BoundedVec<u8, T::MaxMemoLen>,
+    ) -> DispatchResult {
+        ensure!(
+            T::Backend::owner(collection, item).as_ref() == Some(who),
+            Error::<T>::NotItemOwner
+        );
+        if memo.is_empty() {
+            Memos::<T>::remove(collection, item);
+        } else {
+            Memos::<T>::insert(collection, item, (who, memo));
+        }
+        Ok(())
+    }
+
+    /// Returns the memo of `item`, if its current owner wrote one.
+    pub fn memo(
+        collection: &CollectionIdOf<T>,
+        item: &ItemIdOf<T>,
+    ) -> Option<BoundedVec<u8, T::MaxMemoLen>> {
+        let (author, memo) = Memos::<T>::get(collection, item)?;
+        (T::Backend::owner(collection, item) == Some(author)).then_some(memo)
+    }
+}
@@ -0,0 +1,379 @@
+//! Test runtime wiring pallet-contracts, pallet-uniques and the PSP02 chain extension.
+
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_support::{
+    parameter_types,
+    traits::{
+        AsEnsureOriginWithArg,
+        ConstBool,
+        ConstU32,
+        ConstU64,
+        Nothing,
+    },
+    weights::Weight,
+};
+use frame_system::{
+    EnsureRoot,
+    EnsureSigned,
+};
+use pallet_contracts::{
+    chain_extension::{
+        Environment,
+        Ext,
+        InitState,
+        RetVal,
+        SysConfig,
+        UncheckedFrom,
+    },
+    DefaultAddressGenerator,
+    DefaultContractAccessWeight,
+    Frame,
+    Schedule,
+};
+use sp_core::H256;
+use core::cell::RefCell;
+use sp_runtime::{
+    testing::Header,
+    traits::{
+        BlakeTwo256,
+        Convert,
+        IdentityLookup,
+    },
+    AccountId32,
+    DispatchError,
+};
+
+use crate as pallet_contracts_uniques;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = AccountId32;
+pub type Balance = u64;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip,
+        Uniques: pallet_uniques,
+        Contracts: pallet_contracts,
+        ContractsUniques: pallet_contracts_uniques,
+    }
+);
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
+
+pub const GAS_LIMIT: Weight = Weight::from_ref_time(100_000_000_000);
+
+parameter_types! {
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(Weight::from_ref_time(2_000_000_000_000));
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = BlockWeights;
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type RuntimeCall = RuntimeCall;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+impl pallet_uniques::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type CollectionId = u32;
+    type ItemId = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type Locker = ();
+    type CollectionDeposit = ConstU64<2>;
+    type ItemDeposit = ConstU64<1>;
+    type MetadataDepositBase = ConstU64<1>;
+    type AttributeDepositBase = ConstU64<1>;
+    type DepositPerByte = ConstU64<1>;
+    type StringLimit = ConstU32<50>;
+    type KeyLimit = ConstU32<50>;
+    type ValueLimit = ConstU32<50>;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type Helper = ();
+}
+
+parameter_types! {
+    pub MySchedule: Schedule<Test> = Default::default();
+    pub const DeletionWeightLimit: Weight = Weight::from_ref_time(500_000_000_000);
+}
+
+impl Convert<Weight, Balance> for Test {
+    fn convert(w: Weight) -> Balance {
+        w.ref_time()
+    }
+}
+
+impl pallet_contracts::Config for Test {
+    type Time = Timestamp;
+    type Randomness = RandomnessCollectiveFlip;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type CallFilter = Nothing;
+    type CallStack = [Frame<Self>; 31];
+    type WeightPrice = Self;
+    type WeightInfo = ();
+    type ChainExtension = pallet_contracts_uniques::Psp02Extension<Self>;
+    type DeletionQueueDepth = ConstU32<1024>;
+    type DeletionWeightLimit = DeletionWeightLimit;
+    type Schedule = MySchedule;
+    type DepositPerByte = ConstU64<1>;
+    type DepositPerItem = ConstU64<2>;
+    type AddressGenerator = DefaultAddressGenerator;
+    type ContractAccessWeight = DefaultContractAccessWeight<BlockWeights>;
+    type MaxCodeLen = ConstU32<{ 128 * 1024 }>;
+    type MaxStorageKeyLen = ConstU32<128>;
+}
+
+parameter_types! {
+    /// Serves `0x0001`, `0x0002` and `0x0003` as `owner`, `transfer` and `freeze`, as if
+    /// they were legacy IDs.
+    pub const LegacyFuncIds: &'static [(u16, u16)] =
+        &[(0x0001, 0x162d), (0x0002, 0xdb20), (0x0003, 0xce8e)];
+    pub const WatcherGasLimit: Weight = Weight::from_ref_time(10_000_000_000);
+}
+
+impl pallet_contracts_uniques::Config for Test {
+    type Backend = pallet_contracts_uniques::backend::Uniques<Test>;
+    type MaxApprovals = ConstU32<4>;
+    type MaxDelegates = ConstU32<4>;
+    type ProxyCheck = TestProxies;
+    type ConfirmationPeriod = ConstU64<10>;
+    type MaxItemIdScan = ConstU32<4>;
+    type ForbidTransfersToSelf = ConstBool<true>;
+    type MaxCapabilities = ConstU32<4>;
+    type MaxPageLen = ConstU32<2>;
+    type SnapshotOrigin = EnsureRoot<AccountId>;
+    type MaxProofLen = ConstU32<8>;
+    type MaxMemoLen = ConstU32<16>;
+    type MaxBatchLen = ConstU32<4>;
+    type LegacyFuncIds = LegacyFuncIds;
+    type CustomHandler = TestHandler;
+    type MaxDeferredOps = ConstU32<4>;
+    type WatcherGasLimit = WatcherGasLimit;
+    type WatcherDepositLimit = ConstU64<1_000_000>;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static PROXIES: RefCell<Vec<(AccountId, AccountId)>> = RefCell::new(Vec::new());
+}
+
+/// [`pallet_contracts_uniques::ProxyCheck`] backed by a list of `(real, delegate)` pairs.
+pub struct TestProxies;
+
+impl TestProxies {
+    pub fn add(real: AccountId, delegate: AccountId) {
+        PROXIES.with(|proxies| proxies.borrow_mut().push((real, delegate)));
+    }
+
+    pub fn remove(real: &AccountId, delegate: &AccountId) {
+        PROXIES.with(|proxies| {
+            proxies.borrow_mut().retain(|(r, d)| (r, d) != (real, delegate))
+        });
+    }
+}
+
+impl pallet_contracts_uniques::ProxyCheck<AccountId> for TestProxies {
+    fn is_proxy(real: &AccountId, delegate: &AccountId) -> bool {
+        PROXIES.with(|proxies| {
+            proxies.borrow().iter().any(|(r, d)| (r, d) == (real, delegate))
+        })
+    }
+}
+
+/// [`pallet_contracts_uniques::CustomHandler`] serving `0xf001`, which writes the account
+/// the calling contract operates as on the collection in its input.
+pub struct TestHandler;
+
+impl pallet_contracts_uniques::CustomHandler<Test> for TestHandler {
+    fn call<E>(func_id: u16, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+    where
+        E: Ext<T = Test>,
+        <Test as SysConfig>::AccountId:
+            UncheckedFrom<<Test as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    {
+        if func_id != 0xf001 {
+            return Err(DispatchError::Other("Unimplemented func_id"))
+        }
+        let mut env = env.buf_in_buf_out();
+        let collection: u32 = env.read_as()?;
+        let who = ContractsUniques::scoped_account_of(env.ext().address(), func_id, &collection)?;
+        env.write(&who.encode(), false, None)?;
+        Ok(RetVal::Converging(0))
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1_000_000_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Deploys the forwarding fixture and returns its address.
+pub fn deploy_fixture() -> AccountId {
+    deploy_fixture_with_salt(vec![])
+}
+
+/// Deploys another instance of the forwarding fixture and returns its address.
+pub fn deploy_fixture_with_salt(salt: Vec<u8>) -> AccountId {
+    let wasm = wat::parse_str(include_str!("../fixtures/chain_extension.wat")).unwrap();
+    Contracts::bare_instantiate(
+        ALICE,
+        1_000_000,
+        GAS_LIMIT,
+        None,
+        pallet_contracts_primitives::Code::Upload(wasm.into()),
+        vec![],
+        salt,
+        false,
+    )
+    .result
+    .unwrap()
+    .account_id
+}
+
+/// Deploys the watcher fixture, which records the input of its last call, and returns its
+/// address.
+pub fn deploy_watcher() -> AccountId {
+    let wasm = wat::parse_str(include_str!("../fixtures/watcher.wat")).unwrap();
+    Contracts::bare_instantiate(
+        ALICE,
+        1_000_000,
+        GAS_LIMIT,
+        None,
+        pallet_contracts_primitives::Code::Upload(wasm.into()),
+        vec![],
+        vec![],
+        false,
+    )
+    .result
+    .unwrap()
+    .account_id
+}
+
+/// Returns the input of the last call of the watcher fixture at `watcher`, if any.
+pub fn last_watcher_input(watcher: &AccountId) -> Option<Vec<u8>> {
+    Contracts::get_storage(watcher.clone(), vec![0; 32]).unwrap()
+}
+
+/// Returns the caller of the last call of the watcher fixture at `watcher`, if any.
+pub fn last_watcher_caller(watcher: &AccountId) -> Option<AccountId> {
+    let caller = Contracts::get_storage(watcher.clone(), vec![1; 32]).unwrap()?;
+    Some(AccountId::decode(&mut &caller[..]).unwrap())
+}
+
+/// Outcome of calling the chain extension through the fixture.
+#[derive(Debug)]
+pub struct ExtensionCall {
+    pub result: Result<(u32, Vec<u8>), DispatchError>,
+    pub gas_consumed: Weight,
+}
+
+impl ExtensionCall {
+    /// Returns the chain extension output, panicking if the call failed.
+    pub fn output(&self) -> &[u8] {
+        let (ret_val, output) = self.result.as_ref().expect("chain extension call failed");
+        assert_eq!(*ret_val, 0);
+        output
+    }
+
+    /// Decodes the chain extension output as `T`.
+    pub fn decode<T: Decode>(&self) -> T {
+        T::decode(&mut self.output()).expect("output is a valid encoding of T")
+    }
+}
+
+/// Calls `func_id` of the chain extension from `contract` with the SCALE encoded `input`.
+pub fn call_extension(contract: &AccountId, func_id: u16, input: impl Encode) -> ExtensionCall {
+    call_extension_raw(contract, func_id, &input.encode())
+}
+
+/// Calls `func_id` of the chain extension from `contract` with `input` as is.
+pub fn call_extension_raw(contract: &AccountId, func_id: u16, input: &[u8]) -> ExtensionCall {
+    call_extension_with_gas_limit(contract, func_id, input, GAS_LIMIT)
+}
+
+/// Calls `func_id` of the chain extension from `contract` with `input` as is, giving the
+/// call no more than `gas_limit`.
+pub fn call_extension_with_gas_limit(
+    contract: &AccountId,
+    func_id: u16,
+    input: &[u8],
+    gas_limit: Weight,
+) -> ExtensionCall {
+    let mut data = (func_id as u32).encode();
+    data.extend_from_slice(input);
+    let result = Contracts::bare_call(ALICE, contract.clone(), 0, gas_limit, None, data, false);
+    ExtensionCall {
+        result: result.result.map(|ret| {
+            let (ret_val, output) = ret.data.split_at(4);
+            (u32::decode(&mut &ret_val[..]).unwrap(), output.to_vec())
+        }),
+        gas_consumed: result.gas_consumed,
+    }
+}
@@ -0,0 +1,265 @@
+//! Test runtime wiring pallet-contracts, pallet-nfts and the PSP02 chain extension.
+//!
+//! Mirrors [`crate::mock`] with the `Nfts` backend, so the same scenarios can run against
+//! both backends.
+
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_support::{
+    parameter_types,
+    traits::{
+        AsEnsureOriginWithArg,
+        ConstBool,
+        ConstU32,
+        ConstU64,
+        Nothing,
+    },
+    weights::Weight,
+};
+use frame_system::{
+    EnsureRoot,
+    EnsureSigned,
+};
+use pallet_contracts::{
+    DefaultAddressGenerator,
+    DefaultContractAccessWeight,
+    Frame,
+    Schedule,
+};
+use pallet_nfts::PalletFeatures;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{
+        BlakeTwo256,
+        Convert,
+        IdentityLookup,
+    },
+};
+
+use crate as pallet_contracts_uniques;
+pub use crate::mock::{
+    AccountId,
+    Balance,
+    ExtensionCall,
+    TestProxies,
+    ALICE,
+    BOB,
+    CHARLIE,
+    GAS_LIMIT,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip,
+        Nfts: pallet_nfts,
+        Contracts: pallet_contracts,
+        ContractsUniques: pallet_contracts_uniques,
+    }
+);
+
+parameter_types! {
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(Weight::from_ref_time(2_000_000_000_000));
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = BlockWeights;
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type RuntimeCall = RuntimeCall;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+parameter_types! {
+    pub Features: PalletFeatures = PalletFeatures::all_enabled();
+}
+
+impl pallet_nfts::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type CollectionId = u32;
+    type ItemId = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type Locker = ();
+    type CollectionDeposit = ConstU64<2>;
+    type ItemDeposit = ConstU64<1>;
+    type MetadataDepositBase = ConstU64<1>;
+    type AttributeDepositBase = ConstU64<1>;
+    type DepositPerByte = ConstU64<1>;
+    type StringLimit = ConstU32<50>;
+    type KeyLimit = ConstU32<50>;
+    type ValueLimit = ConstU32<50>;
+    type ApprovalsLimit = ConstU32<10>;
+    type MaxTips = ConstU32<10>;
+    type MaxDeadlineDuration = ConstU64<10000>;
+    type Features = Features;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type Helper = ();
+}
+
+parameter_types! {
+    pub MySchedule: Schedule<Test> = Default::default();
+    pub const DeletionWeightLimit: Weight = Weight::from_ref_time(500_000_000_000);
+}
+
+impl Convert<Weight, Balance> for Test {
+    fn convert(w: Weight) -> Balance {
+        w.ref_time()
+    }
+}
+
+impl pallet_contracts::Config for Test {
+    type Time = Timestamp;
+    type Randomness = RandomnessCollectiveFlip;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type CallFilter = Nothing;
+    type CallStack = [Frame<Self>; 31];
+    type WeightPrice = Self;
+    type WeightInfo = ();
+    type ChainExtension = pallet_contracts_uniques::Psp02Extension<Self>;
+    type DeletionQueueDepth = ConstU32<1024>;
+    type DeletionWeightLimit = DeletionWeightLimit;
+    type Schedule = MySchedule;
+    type DepositPerByte = ConstU64<1>;
+    type DepositPerItem = ConstU64<2>;
+    type AddressGenerator = DefaultAddressGenerator;
+    type ContractAccessWeight = DefaultContractAccessWeight<BlockWeights>;
+    type MaxCodeLen = ConstU32<{ 128 * 1024 }>;
+    type MaxStorageKeyLen = ConstU32<128>;
+}
+
+parameter_types! {
+    /// Serves `0x0001`, `0x0002` and `0x0003` as `owner`, `transfer` and `freeze`, as if
+    /// they were legacy IDs.
+    pub const LegacyFuncIds: &'static [(u16, u16)] =
+        &[(0x0001, 0x162d), (0x0002, 0xdb20), (0x0003, 0xce8e)];
+    pub const WatcherGasLimit: Weight = Weight::from_ref_time(10_000_000_000);
+}
+
+impl pallet_contracts_uniques::Config for Test {
+    type Backend = pallet_contracts_uniques::backend::Nfts<Test>;
+    type MaxApprovals = ConstU32<4>;
+    type MaxDelegates = ConstU32<4>;
+    type ProxyCheck = TestProxies;
+    type ConfirmationPeriod = ConstU64<10>;
+    type MaxItemIdScan = ConstU32<4>;
+    type ForbidTransfersToSelf = ConstBool<true>;
+    type MaxCapabilities = ConstU32<4>;
+    type MaxPageLen = ConstU32<2>;
+    type SnapshotOrigin = EnsureRoot<AccountId>;
+    type MaxProofLen = ConstU32<8>;
+    type MaxMemoLen = ConstU32<16>;
+    type MaxBatchLen = ConstU32<4>;
+    type LegacyFuncIds = LegacyFuncIds;
+    type CustomHandler = ();
+    type MaxDeferredOps = ConstU32<4>;
+    type WatcherGasLimit = WatcherGasLimit;
+    type WatcherDepositLimit = ConstU64<1_000_000>;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1_000_000_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Deploys the forwarding fixture and returns its address.
+pub fn deploy_fixture() -> AccountId {
+    deploy_fixture_with_salt(vec![])
+}
+
+/// Deploys another instance of the forwarding fixture and returns its address.
+pub fn deploy_fixture_with_salt(salt: Vec<u8>) -> AccountId {
+    let wasm = wat::parse_str(include_str!("../fixtures/chain_extension.wat")).unwrap();
+    Contracts::bare_instantiate(
+        ALICE,
+        1_000_000,
+        GAS_LIMIT,
+        None,
+        pallet_contracts_primitives::Code::Upload(wasm.into()),
+        vec![],
+        salt,
+        false,
+    )
+    .result
+    .unwrap()
+    .account_id
+}
+
+/// Calls `func_id` of the chain extension from `contract` with the SCALE encoded `input`.
+pub fn call_extension(contract: &AccountId, func_id: u16, input: impl Encode) -> ExtensionCall {
+    let mut data = (func_id as u32).encode();
+    input.encode_to(&mut data);
+    let result = Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, None, data, false);
+    ExtensionCall {
+        result: result.result.map(|ret| {
+            let (ret_val, output) = ret.data.split_at(4);
+            (u32::decode(&mut &ret_val[..]).unwrap(), output.to_vec())
+        }),
+        gas_consumed: result.gas_consumed,
+    }
+}
@@ -0,0 +1,17 @@
+//! Counters of the mutations contracts make to collections.
+//!
+//! Contracts running an order book off-chain sign orders against the state of a
+//! collection they read earlier, and have to find out whether it changed before settling
+//! them. Every call of the extension a contract makes that changes a collection bumps the
+//! nonce of the contract and the collection, so comparing a single counter replaces
+//! reading back every item an order depends on. Calls that fail or report a status other
+//! than success change nothing and leave the nonce as it is.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Records that `contract` made a call changing `collection`.
+    pub(crate) fn bump_nonce(contract: &T::AccountId, collection: &CollectionIdOf<T>) {
+        Nonces::<T>::mutate(contract, collection, |nonce| *nonce = nonce.wrapping_add(1));
+    }
+}
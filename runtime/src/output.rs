@@ -0,0 +1,213 @@
+//! Query responses built in the buffer handed to the contract.
+//!
+//! The extension hands its output to `pallet_contracts` as a byte slice, which is copied
+//! into the contract's memory as is. Responses are therefore built in a single buffer
+//! that is written without further copies: sequences are encoded while their items are
+//! produced instead of being collected first, and stored values are copied from storage
+//! as they are instead of being decoded only to be encoded again. Buffers are allocated
+//! with room for the longest response upfront, so they never grow while being written.
+//!
+//! Tooling built for Solidity struggles with nested SCALE options, so the most common
+//! queries can also answer in a [`Format::Flat`] layout, selected by a byte the contract
+//! appends to the input of the query.
+
+use codec::{
+    Compact,
+    Decode,
+    Encode,
+    MaxEncodedLen,
+};
+use sp_std::{
+    vec,
+    vec::Vec,
+};
+
+use super::*;
+
+/// The most bytes the compact encoding of a `u32` length takes.
+const MAX_LEN_PREFIX: usize = 5;
+
+/// The byte selecting the [`Format::Scale`] layout, the default.
+pub(crate) const SCALE: u8 = 0;
+/// The byte selecting the [`Format::Flat`] layout.
+pub(crate) const FLAT: u8 = 1;
+
+/// The layout of the output of a query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    /// The SCALE encoding of the output.
+    Scale,
+    /// A status byte telling whether the queried value exists, followed by its fields at
+    /// fixed offsets. The fields of absent values are zero-filled, and every optional field
+    /// is preceded by a byte telling whether it is set.
+    Flat,
+}
+
+/// Query outputs made of fixed-size fields, which can be laid out as [`Format::Flat`].
+pub(crate) trait Flatten: Encode {
+    /// Returns the length of the fields, whether the value exists or not.
+    fn fields_len() -> usize;
+
+    /// Returns whether the value exists, as reported by the status byte.
+    fn is_present(&self) -> bool {
+        true
+    }
+
+    /// Appends the fields of the value, zero-filled if it does not exist.
+    fn fields_to(&self, dest: &mut Vec<u8>);
+}
+
+impl Flatten for bool {
+    fn fields_len() -> usize {
+        1
+    }
+
+    fn fields_to(&self, dest: &mut Vec<u8>) {
+        self.encode_to(dest)
+    }
+}
+
+impl<V: Encode + MaxEncodedLen> Flatten for Option<V> {
+    fn fields_len() -> usize {
+        V::max_encoded_len()
+    }
+
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+
+    fn fields_to(&self, dest: &mut Vec<u8>) {
+        match self {
+            Some(value) => value.encode_to(dest),
+            None => dest.resize(dest.len() + Self::fields_len(), 0),
+        }
+    }
+}
+
+impl<V: Encode + MaxEncodedLen> Flatten for (bool, Option<V>) {
+    fn fields_len() -> usize {
+        2 + <Option<V>>::fields_len()
+    }
+
+    fn fields_to(&self, dest: &mut Vec<u8>) {
+        let (flag, value) = self;
+        flag.encode_to(dest);
+        value.is_present().encode_to(dest);
+        value.fields_to(dest);
+    }
+}
+
+/// An encoded response, starting at `start` of `buf`.
+pub(crate) struct Output {
+    buf: Vec<u8>,
+    start: usize,
+}
+
+impl Output {
+    /// Encodes `value`.
+    pub(crate) fn encode(value: &impl Encode) -> Self {
+        Self { buf: value.encode(), start: 0 }
+    }
+
+    /// Encodes `value` in `format`.
+    pub(crate) fn encode_in<F: Flatten>(value: &F, format: Format) -> Self {
+        match format {
+            Format::Scale => Self::encode(value),
+            Format::Flat => {
+                let mut buf = Vec::with_capacity(1 + F::fields_len());
+                buf.push(u8::from(value.is_present()));
+                value.fields_to(&mut buf);
+                Self { buf, start: 0 }
+            },
+        }
+    }
+
+    /// Encodes `items` as a vector, without collecting them first.
+    ///
+    /// The number of items is only known once they are encoded, so they are encoded after
+    /// room for the longest length prefix, and the prefix is written right in front of them.
+    /// The buffer has room for as many items as the iterator may yield at most.
+    pub(crate) fn encode_seq<I>(items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Encode + MaxEncodedLen,
+    {
+        let items = items.into_iter();
+        let (min, max) = items.size_hint();
+        let capacity = max.unwrap_or(min).saturating_mul(I::Item::max_encoded_len());
+        let mut buf = Vec::with_capacity(MAX_LEN_PREFIX.saturating_add(capacity));
+        buf.resize(MAX_LEN_PREFIX, 0);
+        let mut len: u32 = 0;
+        for item in items {
+            item.encode_to(&mut buf);
+            len = len.saturating_add(1);
+        }
+        let prefix = Compact(len).encode();
+        let start = MAX_LEN_PREFIX - prefix.len();
+        buf[start..MAX_LEN_PREFIX].copy_from_slice(&prefix);
+        Self { buf, start }
+    }
+
+    /// Copies the value stored under `key` after `prefix`, or returns `None` if nothing is
+    /// stored.
+    fn stored(key: &[u8], prefix: &[u8]) -> Option<Self> {
+        let len = sp_io::storage::read(key, &mut [], 0)? as usize;
+        let mut buf = vec![0; prefix.len() + len];
+        buf[..prefix.len()].copy_from_slice(prefix);
+        sp_io::storage::read(key, &mut buf[prefix.len()..], 0);
+        Some(Self { buf, start: 0 })
+    }
+
+    /// The value stored under `key` of an `OptionQuery` storage, encoded as an `Option`.
+    pub(crate) fn stored_option(key: &[u8]) -> Self {
+        Self::stored(key, &[1]).unwrap_or_else(|| Self::encode(&None::<()>))
+    }
+
+    /// The value stored under `key` of a `ValueQuery` storage, or `default` if nothing is
+    /// stored.
+    pub(crate) fn stored_or(key: &[u8], default: &impl Encode) -> Self {
+        Self::stored(key, &[]).unwrap_or_else(|| Self::encode(default))
+    }
+
+    /// Returns the encoded response.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.buf[self.start..]
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Encodes the items `owner` still owns and approved `delegate` to transfer, as
+    /// returned by [`Pallet::approvals_to`].
+    pub(crate) fn approvals_to_output(owner: &T::AccountId, delegate: &T::AccountId) -> Output {
+        Output::encode_seq(Self::iter_approvals_to(owner, delegate))
+    }
+
+    /// Encodes the memo of `item`, as returned by [`Pallet::memo`].
+    pub(crate) fn memo_output(collection: &CollectionIdOf<T>, item: &ItemIdOf<T>) -> Output {
+        let none = || Output::encode(&None::<()>);
+        let key = Memos::<T>::hashed_key_for(collection, item);
+        let mut stored = match Output::stored(&key, &[]) {
+            Some(stored) => stored,
+            None => return none(),
+        };
+        let mut memo = &stored.buf[..];
+        let author = match T::AccountId::decode(&mut memo) {
+            Ok(author) => author,
+            Err(_) => return none(),
+        };
+        let author_len = stored.buf.len() - memo.len();
+        if T::Backend::owner(collection, item) != Some(author) {
+            return none()
+        }
+        // The `Some` tag takes the place of the last byte of the author, which is no longer
+        // needed.
+        match author_len.checked_sub(1) {
+            Some(start) => {
+                stored.buf[start] = 1;
+                stored.start = start;
+                stored
+            },
+            None => none(),
+        }
+    }
+}
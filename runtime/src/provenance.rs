@@ -0,0 +1,33 @@
+//! Content hashes anchored to items when they are minted.
+//!
+//! Metadata and attributes stay mutable, so a hash kept there says little about where an
+//! item comes from. A contract minting an item can instead anchor a content hash, e.g. of
+//! the original artwork, which is stored apart from the metadata and never changes. Items
+//! minted or burned through the extension drop the hash of an earlier item with their id.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Mints the first unused item id of `collection` to `owner` on behalf of `who`, like
+    /// [`Pallet::mint_next`], and anchors `hash` to the item.
+    pub(crate) fn mint_next_with_provenance(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        owner: &T::AccountId,
+        hash: T::Hash,
+    ) -> Result<ItemIdOf<T>, DispatchError> {
+        let item = Self::mint_next(who, collection, owner)?;
+        Provenance::<T>::insert(collection, item, hash);
+        Ok(item)
+    }
+
+    /// Returns whether `hash` is the content hash anchored to the existing `item`.
+    pub fn verify_provenance(
+        collection: &CollectionIdOf<T>,
+        item: &ItemIdOf<T>,
+        hash: &T::Hash,
+    ) -> bool {
+        T::Backend::owner(collection, item).is_some() &&
+            Provenance::<T>::get(collection, item).as_ref() == Some(hash)
+    }
+}
@@ -0,0 +1,48 @@
+//! Lets a contract act for a user who made it their proxy.
+//!
+//! A contract opts into acting for a user through the `act_for` function ID; every
+//! subsequent call then operates on the user's items, as long as [`ProxyCheck`] keeps
+//! confirming the user granted the contract a suitable proxy.
+
+#[cfg(feature = "proxy")]
+use frame_support::{
+    pallet_prelude::*,
+    traits::Contains,
+};
+#[cfg(feature = "proxy")]
+use sp_runtime::traits::Zero;
+
+/// Decides whether a contract may act for a user.
+pub trait ProxyCheck<AccountId> {
+    /// Returns whether `delegate` may act for `real`.
+    fn is_proxy(real: &AccountId, delegate: &AccountId) -> bool;
+}
+
+/// Never lets a contract act for anyone but itself.
+impl<AccountId> ProxyCheck<AccountId> for () {
+    fn is_proxy(_real: &AccountId, _delegate: &AccountId) -> bool {
+        false
+    }
+}
+
+/// [`ProxyCheck`] backed by `pallet_proxy`.
+///
+/// Only proxies without an announcement delay and whose type is accepted by `Filter`,
+/// typically `Any` and `NonTransfer`, let the contract act for the user.
+#[cfg(feature = "proxy")]
+pub struct PalletProxy<T, Filter>(PhantomData<(T, Filter)>);
+
+#[cfg(feature = "proxy")]
+impl<T, Filter> ProxyCheck<T::AccountId> for PalletProxy<T, Filter>
+where
+    T: pallet_proxy::Config,
+    Filter: Contains<T::ProxyType>,
+{
+    fn is_proxy(real: &T::AccountId, delegate: &T::AccountId) -> bool {
+        pallet_proxy::Proxies::<T>::get(real).0.iter().any(|proxy| {
+            &proxy.delegate == delegate &&
+                proxy.delay.is_zero() &&
+                Filter::contains(&proxy.proxy_type)
+        })
+    }
+}
@@ -5,32 +5,25 @@ use codec::{
 };
 use frame_support::{
     DefaultNoBound,
-    dispatch::RawOrigin,
     log::{
         error,
         trace,
     },
     pallet_prelude::*,
-    traits::tokens::nonfungibles::{
-        Inspect,
-        Transfer,
-    },
 };
 pub use sp_core::{
 	crypto::{
 		Wraps,
 	},
 };
-use pallet_uniques::{
-    self,
-    WeightInfo,
-};
 use pallet_contracts::chain_extension::{
     ChainExtension,
+    ChargedAmount,
     Environment,
     Ext,
     InitState,
     RetVal,
+    state::BufInBufOut,
     SysConfig,
     UncheckedFrom,
     RegisteredChainExtension
@@ -38,13 +31,20 @@ use pallet_contracts::chain_extension::{
 use sp_runtime::{
     traits::{
         Saturating,
-        StaticLookup,
-        Zero,
     },
     DispatchError,
 };
+use frame_support::storage::with_transaction;
+use sp_runtime::TransactionOutcome;
+use sp_std::vec::Vec;
 
 use super::*;
+use crate::output::{
+    Format,
+    Output,
+    FLAT,
+    SCALE,
+};
 
 #[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
 struct Psp02PriceOf<ItemId, AccountId> {
@@ -65,6 +65,157 @@ struct Psp02TransferInput<ItemId, CollectionId, AccountId> {
     dest: AccountId
 }
 
+#[derive(Decode, MaxEncodedLen)]
+#[codec(mel_bound(ItemId: MaxEncodedLen, CollectionId: MaxEncodedLen, AccountId: MaxEncodedLen))]
+struct Psp02TransferBatchInput<ItemId, CollectionId, AccountId, MaxLen: Get<u32>> {
+    collection_id: CollectionId,
+    transfers: BoundedVec<(ItemId, AccountId), MaxLen>,
+    best_effort: bool
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02ItemInput<ItemId, CollectionId> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02CollectionInput<CollectionId> {
+    collection_id: CollectionId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02CollectionsOwnedInput<CollectionId, AccountId> {
+    account: AccountId,
+    start_after: Option<CollectionId>,
+    limit: u32
+}
+
+#[derive(Decode, MaxEncodedLen)]
+#[codec(mel_bound(AccountId: MaxEncodedLen, CollectionId: MaxEncodedLen))]
+struct Psp02GrantCapabilityInput<CollectionId, AccountId, MaxFuncIds: Get<u32>> {
+    grantee: AccountId,
+    collection_id: CollectionId,
+    func_ids: BoundedVec<u16, MaxFuncIds>
+}
+
+#[derive(Decode, MaxEncodedLen)]
+#[codec(mel_bound(
+    CollectionId: MaxEncodedLen,
+    BlockNumber: MaxEncodedLen,
+    AccountId: MaxEncodedLen,
+    Hash: MaxEncodedLen
+))]
+struct Psp02SnapshotMembershipInput<CollectionId, BlockNumber, AccountId, Hash, MaxLen: Get<u32>> {
+    collection_id: CollectionId,
+    block_number: BlockNumber,
+    account: AccountId,
+    proof: BoundedVec<Hash, MaxLen>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02AllowanceInput<ItemId, CollectionId, AccountId> {
+    collection_id: CollectionId,
+    owner: AccountId,
+    operator: AccountId,
+    item_id: Option<ItemId>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02BurnInput<ItemId, CollectionId, AccountId> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    expected_owner: AccountId
+}
+
+#[derive(Decode, MaxEncodedLen)]
+#[codec(mel_bound(CollectionId: MaxEncodedLen, ItemId: MaxEncodedLen))]
+struct Psp02MemoInput<ItemId, CollectionId, MaxLen: Get<u32>> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    memo: BoundedVec<u8, MaxLen>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02CooldownInput<CollectionId, BlockNumber> {
+    collection_id: CollectionId,
+    cooldown: Option<BlockNumber>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02WatcherInput<CollectionId, AccountId> {
+    collection_id: CollectionId,
+    watcher: Option<AccountId>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02CapabilitiesInput<AccountId> {
+    grantor: AccountId,
+    grantee: AccountId
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02MintNextInput<CollectionId, AccountId> {
+    collection_id: CollectionId,
+    owner: AccountId
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02MintNextWithProvenanceInput<CollectionId, AccountId, Hash> {
+    collection_id: CollectionId,
+    owner: AccountId,
+    hash: Hash
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02ProvenanceInput<ItemId, CollectionId, Hash> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    hash: Hash
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02AdminOpInput<CollectionId, Op> {
+    collection_id: CollectionId,
+    op: Op
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02DeferredOpInput {
+    op_id: u32
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02ApprovalInput<ItemId, CollectionId, AccountId> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    delegate: AccountId
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02FreezeInput<ItemId, CollectionId> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    reason: Option<u32>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02DelegateInput<AccountId> {
+    delegate: AccountId
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp02ActForInput<AccountId> {
+    real: Option<AccountId>
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Psp02MetadataInput<ItemId, CollectionId> {
+    collection_id: CollectionId,
+    item_id: ItemId,
+    data: Vec<u8>
+}
+
 #[derive(DefaultNoBound)]
 pub struct Psp02Extension<T: Config> {
     _phantom: PhantomData<T>,
@@ -77,20 +228,90 @@ fn convert_err(err_msg: &'static str) -> impl FnOnce(DispatchError) -> DispatchE
             "PSP02 Transfer failed:{:?}",
             err
         );
+        // Debug builds hand the original error to the contract through `last_error`.
+        if cfg!(feature = "debug") {
+            return err
+        }
         DispatchError::Other(err_msg)
     }
 }
 
+/// Status codes returned to contracts for failures they are expected to handle, as
+/// opposed to errors that trap the calling contract. The codes are frozen in
+/// [`crate::status`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+enum Status {
+    Success = status::SUCCESS,
+    TransferToZeroAccount = status::TRANSFER_TO_ZERO_ACCOUNT,
+    TransferToOwner = status::TRANSFER_TO_OWNER,
+    TransferToSelf = status::TRANSFER_TO_SELF,
+    /// The call failed and was reverted; `last_error` returns why.
+    #[cfg(feature = "debug")]
+    Failed = status::FAILED,
+    TransferCooldown = status::TRANSFER_COOLDOWN,
+    OwnerMismatch = status::OWNER_MISMATCH,
+    CollectionDestroyed = status::COLLECTION_DESTROYED,
+    TransferFailed = status::TRANSFER_FAILED,
+}
+
+/// The function ID of `transfer`. A capability to call it also covers `transfer_batch`.
+pub(crate) const TRANSFER: u16 = 0xdb20;
+/// The function ID of `transfer_batch`.
+const TRANSFER_BATCH: u16 = 0x3df4;
+
 /// We're using enums for function IDs because contrary to raw u16 it enables
 /// exhaustive matching, which results in cleaner code.
 enum FuncId {
     Query(Query),
-    Transfer
+    Transfer,
+    TransferBatch,
+    Approve,
+    CancelApproval,
+    SetMetadata,
+    ClearMetadata,
+    RevokeAllApprovals,
+    AttestOwnership,
+    Freeze,
+    Thaw,
+    ActFor,
+    AdminOp,
+    ConfirmAdminOp,
+    CancelAdminOp,
+    NextFreeItemId,
+    MintNext,
+    GrantCapability,
+    SetMemo,
+    SetTransferCooldown,
+    SubmitDeferredOp,
+    CancelDeferredOp,
+    SetWatcher,
+    Burn,
+    MintNextWithProvenance
 }
 
 #[derive(Debug)]
 enum Query {
-    Owner
+    Owner,
+    ApprovalsTo,
+    VerifyAttestation,
+    FreezeStatus,
+    ActingFor,
+    PendingAdminOp,
+    AdminConfirmer,
+    CollectionsOwned,
+    Capabilities,
+    VerifySnapshotMembership,
+    Memo,
+    TransferCooldown,
+    FreeHolding,
+    DeferredOpStatus,
+    Watcher,
+    Allowance,
+    VerifyProvenance,
+    CollectionNonce,
+    #[cfg(feature = "debug")]
+    LastError
 }
 
 impl TryFrom<u16> for FuncId {
@@ -101,7 +322,54 @@ impl TryFrom<u16> for FuncId {
             // Note: We use the first two bytes of PSP22 interface selectors as function IDs,
             // While we can use anything here, it makes sense from a convention perspective.
             0x162d => Self::Query(Query::Owner),
-            0xdb20 => Self::Transfer,
+            TRANSFER => Self::Transfer,
+            // The remaining IDs are the first two bytes of the selectors of the matching
+            // messages of the ink! side, derived the same way ink! derives trait selectors.
+            // None of them may fall into `custom::CUSTOM_FUNC_IDS`, which is reserved for
+            // the function IDs runtimes add.
+            0x1932 => Self::Approve,
+            0x9c69 => Self::CancelApproval,
+            0xb5e2 => Self::SetMetadata,
+            0x3e09 => Self::ClearMetadata,
+            0x817c => Self::Query(Query::ApprovalsTo),
+            0x2612 => Self::RevokeAllApprovals,
+            0x145a => Self::AttestOwnership,
+            0xe8c5 => Self::Query(Query::VerifyAttestation),
+            0xce8e => Self::Freeze,
+            0xa49a => Self::Thaw,
+            0x4cb3 => Self::Query(Query::FreezeStatus),
+            0x0d2d => Self::ActFor,
+            0xb65e => Self::Query(Query::ActingFor),
+            0x5873 => Self::AdminOp,
+            0xe63e => Self::ConfirmAdminOp,
+            0xd123 => Self::CancelAdminOp,
+            0xc29f => Self::Query(Query::PendingAdminOp),
+            0x16fb => Self::Query(Query::AdminConfirmer),
+            0x32ae => Self::NextFreeItemId,
+            0xaeed => Self::MintNext,
+            0xbb93 => Self::Query(Query::CollectionsOwned),
+            0x253d => Self::GrantCapability,
+            0xe253 => Self::Query(Query::Capabilities),
+            0xeaf3 => Self::Query(Query::VerifySnapshotMembership),
+            0xc60a => Self::SetMemo,
+            0x05a6 => Self::Query(Query::Memo),
+            0x813e => Self::SetTransferCooldown,
+            TRANSFER_BATCH => Self::TransferBatch,
+            0x1e27 => Self::Query(Query::TransferCooldown),
+            0xe94b => Self::Query(Query::FreeHolding),
+            0x267a => Self::SubmitDeferredOp,
+            0x47b5 => Self::Query(Query::DeferredOpStatus),
+            0x9c83 => Self::CancelDeferredOp,
+            0x5723 => Self::SetWatcher,
+            0x47e5 => Self::Query(Query::Watcher),
+            0x6b71 => Self::Burn,
+            // The first two bytes of the selector of `allowance` of PSP34.
+            0x4790 => Self::Query(Query::Allowance),
+            0x615c => Self::MintNextWithProvenance,
+            0x27cd => Self::Query(Query::VerifyProvenance),
+            0xdfc4 => Self::Query(Query::CollectionNonce),
+            #[cfg(feature = "debug")]
+            0x1ae3 => Self::Query(Query::LastError),
             _ => {
                 error!("Called an unregistered `func_id`: {:}", func_id);
                 return Err(DispatchError::Other("Unimplemented func_id"))
@@ -112,41 +380,207 @@ impl TryFrom<u16> for FuncId {
     }
 }
 
+/// Reads the input of a query, followed by the byte selecting the format of its output,
+/// if the contract appended one.
+///
+/// Both are decoded from a single read of the input. Any other bytes after the input of
+/// the query are rejected, as is an input too long to hold a format byte at all.
+fn read_query_input<T, I, E>(
+    env: &mut Environment<E, BufInBufOut>,
+) -> Result<(I, Format), DispatchError>
+where
+    T: Config,
+    I: Decode + MaxEncodedLen,
+    E: Ext<T = T>,
+{
+    let buf = env.read(I::max_encoded_len().saturating_add(1) as u32)?;
+    let mut rest = &buf[..];
+    let input = I::decode(&mut rest)
+        .map_err(|_| DispatchError::from(pallet_contracts::Error::<T>::DecodingFailed))?;
+    let trailing = (env.in_len() as usize).saturating_sub(buf.len() - rest.len());
+    let format = match (trailing, rest) {
+        (0, _) => Format::Scale,
+        (1, [SCALE]) => Format::Scale,
+        (1, [FLAT]) => Format::Flat,
+        (1, _) => return Err(DispatchError::Other("ChainExtension got an unknown output format")),
+        _ => return Err(pallet_contracts::Error::<T>::DecodingFailed.into()),
+    };
+    Ok((input, format))
+}
+
 fn query<T, E>(
     func_id: Query,
-    env: Environment<E, InitState>,
+    env: &mut Environment<E, BufInBufOut>,
 ) -> Result<(), DispatchError>
 where
-    T: pallet_uniques::Config + pallet_contracts::Config,
+    T: Config,
     <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
     E: Ext<T = T>,
 {
-    let mut env = env.buf_in_buf_out();
+    let weight = match func_id {
+        Query::Owner => T::WeightInfo::owner(),
+        Query::ApprovalsTo => T::WeightInfo::approvals_to(T::MaxApprovals::get()),
+        Query::VerifyAttestation => T::WeightInfo::verify_attestation(),
+        Query::FreezeStatus => T::WeightInfo::freeze_status(),
+        Query::ActingFor => T::WeightInfo::acting_for(),
+        Query::PendingAdminOp => T::WeightInfo::pending_admin_op(),
+        Query::AdminConfirmer => T::WeightInfo::admin_confirmer(),
+        Query::CollectionsOwned => T::WeightInfo::collections_owned(T::MaxPageLen::get()),
+        Query::Capabilities => T::WeightInfo::capabilities(),
+        Query::VerifySnapshotMembership =>
+            T::WeightInfo::verify_snapshot_membership(T::MaxProofLen::get()),
+        Query::Memo => T::WeightInfo::memo(),
+        Query::TransferCooldown => T::WeightInfo::transfer_cooldown(),
+        Query::FreeHolding => T::WeightInfo::free_holding(),
+        Query::DeferredOpStatus => T::WeightInfo::deferred_op_status(),
+        Query::Watcher => T::WeightInfo::watcher(),
+        Query::Allowance => T::WeightInfo::allowance(),
+        Query::VerifyProvenance => T::WeightInfo::verify_provenance(),
+        Query::CollectionNonce => T::WeightInfo::collection_nonce(),
+        #[cfg(feature = "debug")]
+        Query::LastError => T::WeightInfo::last_error(),
+    };
+    let charged_weight = charge::<T, E>(env, weight)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|query|{:?} / charge_weight:{:?}",
+        func_id,
+        charged_weight
+    );
+
     let result = match func_id {
         Query::Owner => {
-            let input: Psp02TransferInput<T::ItemId, T::CollectionId, T::AccountId> = env.read_as()?;
-            let Psp02TransferInput {collection_id,  item_id, dest: _} = input;
-            <pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::owner(&collection_id,&item_id)
+            let (input, format): (Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>>, _) =
+                read_query_input::<T, _, E>(env)?;
+            let Psp02ItemInput {collection_id, item_id} = input;
+            Output::encode_in(&T::Backend::owner(&collection_id, &item_id), format)
         }
-    }
-    .encode();
+        Query::ApprovalsTo => {
+            let input: Psp02DelegateInput<T::AccountId> = env.read_as()?;
+            let Psp02DelegateInput {delegate} = input;
+            let owner = Pallet::<T>::account_of(env.ext().address())?;
+            Pallet::<T>::approvals_to_output(&owner, &delegate)
+        }
+        Query::VerifyAttestation => {
+            let attestation: OwnershipAttestationOf<T> = env.read_as()?;
+            Output::encode(&Pallet::<T>::verify_attestation(&attestation))
+        }
+        Query::FreezeStatus => {
+            let (input, format): (Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>>, _) =
+                read_query_input::<T, _, E>(env)?;
+            let Psp02ItemInput {collection_id, item_id} = input;
+            Output::encode_in(&Pallet::<T>::freeze_status(&collection_id, &item_id), format)
+        }
+        Query::ActingFor =>
+            Output::stored_option(&ActingFor::<T>::hashed_key_for(env.ext().address())),
+        Query::PendingAdminOp => {
+            let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+            Output::stored_option(&PendingAdminOps::<T>::hashed_key_for(input.collection_id))
+        }
+        Query::AdminConfirmer => {
+            let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+            Output::stored_option(&AdminConfirmers::<T>::hashed_key_for(input.collection_id))
+        }
+        Query::CollectionsOwned => {
+            let input: Psp02CollectionsOwnedInput<CollectionIdOf<T>, T::AccountId> =
+                env.read_as()?;
+            let Psp02CollectionsOwnedInput {account, start_after, limit} = input;
+            let limit = limit.min(T::MaxPageLen::get());
+            Output::encode(&T::Backend::collections_owned(&account, start_after.as_ref(), limit))
+        }
+        Query::Capabilities => {
+            let input: Psp02CapabilitiesInput<T::AccountId> = env.read_as()?;
+            let Psp02CapabilitiesInput {grantor, grantee} = input;
+            let key = Capabilities::<T>::hashed_key_for(grantor, grantee);
+            Output::stored_or(&key, &Vec::<(CollectionIdOf<T>, u16)>::new())
+        }
+        Query::VerifySnapshotMembership => {
+            let input: Psp02SnapshotMembershipInput<
+                CollectionIdOf<T>,
+                T::BlockNumber,
+                T::AccountId,
+                T::Hash,
+                T::MaxProofLen,
+            > = env.read_as()?;
+            let Psp02SnapshotMembershipInput {collection_id, block_number, account, proof} = input;
+            Output::encode(&Pallet::<T>::verify_snapshot_membership(
+                &collection_id,
+                &block_number,
+                &account,
+                &proof,
+            ))
+        }
+        Query::Memo => {
+            let input: Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as()?;
+            let Psp02ItemInput {collection_id, item_id} = input;
+            Pallet::<T>::memo_output(&collection_id, &item_id)
+        }
+        Query::TransferCooldown => {
+            let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+            Output::stored_option(&TransferCooldowns::<T>::hashed_key_for(input.collection_id))
+        }
+        Query::FreeHolding => {
+            let (input, format): (Psp02CollectionInput<CollectionIdOf<T>>, _) =
+                read_query_input::<T, _, E>(env)?;
+            Output::encode_in(&T::Backend::free_holding(&input.collection_id), format)
+        }
+        Query::DeferredOpStatus => {
+            let input: Psp02DeferredOpInput = env.read_as()?;
+            Output::stored_option(&DeferredOps::<T>::hashed_key_for(input.op_id))
+        }
+        Query::Watcher => {
+            let (input, format): (Psp02CollectionInput<CollectionIdOf<T>>, _) =
+                read_query_input::<T, _, E>(env)?;
+            let watcher = Watchers::<T>::get(input.collection_id).map(|(_, watcher)| watcher);
+            Output::encode_in(&watcher, format)
+        }
+        Query::Allowance => {
+            let (input, format): (
+                Psp02AllowanceInput<ItemIdOf<T>, CollectionIdOf<T>, T::AccountId>,
+                _,
+            ) = read_query_input::<T, _, E>(env)?;
+            let Psp02AllowanceInput {collection_id, owner, operator, item_id} = input;
+            let allowed =
+                Pallet::<T>::allowance(&collection_id, &owner, &operator, item_id.as_ref());
+            Output::encode_in(&allowed, format)
+        }
+        Query::VerifyProvenance => {
+            let (input, format): (
+                Psp02ProvenanceInput<ItemIdOf<T>, CollectionIdOf<T>, T::Hash>,
+                _,
+            ) = read_query_input::<T, _, E>(env)?;
+            let Psp02ProvenanceInput {collection_id, item_id, hash} = input;
+            let verified = Pallet::<T>::verify_provenance(&collection_id, &item_id, &hash);
+            Output::encode_in(&verified, format)
+        }
+        Query::CollectionNonce => {
+            let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+            let key = Nonces::<T>::hashed_key_for(env.ext().address(), input.collection_id);
+            Output::stored_or(&key, &0u64)
+        }
+        #[cfg(feature = "debug")]
+        Query::LastError =>
+            Output::stored_option(&LastErrors::<T>::hashed_key_for(env.ext().address())),
+    };
     trace!(
         target: "runtime",
         "[ChainExtension] PSP22::{:?}",
         func_id
     );
-    env.write(&result, false, None)
+    write_output::<T, E>(env, result.as_bytes())
         .map_err(convert_err("ChainExtension failed to call PSP22 query"))
 }
 
-fn transfer<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+/// Charges `base_weight` plus the overhead of going from the contract layer to the
+/// substrate layer.
+fn charge<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    base_weight: Weight,
+) -> Result<ChargedAmount, DispatchError>
 where
-    T: pallet_uniques::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    T: Config,
     E: Ext<T = T>,
 {
-    let mut env = env.buf_in_buf_out();
-    let base_weight = <T as pallet_uniques::Config>::WeightInfo::transfer();
     // debug_message weight is a good approximation of the additional overhead of going from
     // contract layer to substrate layer.
     let overhead = Weight::from_ref_time(
@@ -154,53 +588,1190 @@ where
             .host_fn_weights
             .debug_message,
     );
-    let charged_weight = env.charge_weight(base_weight.saturating_add(overhead))?;
+    env.charge_weight(base_weight.saturating_add(overhead))
+}
+
+/// Writes `output` for the contract, and notes it in the debug buffer in debug builds.
+fn write_output<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    output: &[u8],
+) -> Result<(), DispatchError>
+where
+    T: Config,
+    E: Ext<T = T>,
+{
+    #[cfg(feature = "debug")]
+    debug_note(env.ext(), "output", &[output]);
+    env.write(output, false, None)
+}
+
+/// Charges the weight of forgetting the approvals of an owner with `MaxDelegates`
+/// delegates, calls `forget` and refunds the weight of the delegates it did not look at.
+fn forget_approvals<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    forget: impl FnOnce() -> Result<u32, DispatchError>,
+) -> Result<(), DispatchError>
+where
+    T: Config,
+    E: Ext<T = T>,
+{
+    let charged = env.charge_weight(T::WeightInfo::forget_item(T::MaxDelegates::get()))?;
+    let delegates = forget()?;
+    env.adjust_weight(charged, T::WeightInfo::forget_item(delegates));
+    Ok(())
+}
+
+/// Returns the account the calling contract operates as for the current function ID on
+/// `collection`.
+fn account_for<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    collection: &CollectionIdOf<T>,
+) -> Result<T::AccountId, DispatchError>
+where
+    T: Config,
+    E: Ext<T = T>,
+{
+    let func_id = match current_func_id::<T>(env.func_id()) {
+        // A batch is a series of transfers, so the capability to call `transfer` covers it.
+        TRANSFER_BATCH => TRANSFER,
+        func_id => func_id,
+    };
+    Pallet::<T>::scoped_account_of(env.ext().address(), func_id, collection)
+}
+
+/// Bumps the nonce of the calling contract and `collection` after a call changed the
+/// collection.
+fn note_mutation<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    collection: &CollectionIdOf<T>,
+) -> Result<(), DispatchError>
+where
+    T: Config,
+    E: Ext<T = T>,
+{
+    env.charge_weight(T::WeightInfo::bump_nonce())?;
+    Pallet::<T>::bump_nonce(env.ext().address(), collection);
+    Ok(())
+}
+
+/// Returns whether `collection` was destroyed or is queued for destruction, charging for
+/// the lookup.
+fn collection_destroyed<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    collection: &CollectionIdOf<T>,
+) -> Result<bool, DispatchError>
+where
+    T: Config,
+    E: Ext<T = T>,
+{
+    env.charge_weight(T::WeightInfo::collection_destroyed())?;
+    Ok(Pallet::<T>::is_destroyed(collection))
+}
+
+fn transfer<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::transfer_weight().saturating_add(T::WeightInfo::transfer()),
+    )?;
     trace!(
         target: "runtime",
         "[ChainExtension]|call|transfer / charge_weight:{:?}",
         charged_weight
     );
 
-    let input: Psp02TransferInput<T::ItemId, T::CollectionId, T::AccountId> = env.read_as()?;
+    let input: Psp02TransferInput<ItemIdOf<T>, CollectionIdOf<T>, T::AccountId> = env.read_as()?;
     let Psp02TransferInput {collection_id,  item_id, dest} = input;
-    let sender = env.ext().caller();
-
-    <pallet_uniques::Pallet<T> as Transfer<T::AccountId>>::transfer(
-        &collection_id,
-        &item_id,
-        &dest
-    )
-    .map_err(convert_err("ChainExtension failed to call transfer"))?;
+    let who = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
+    }
+    let status = transfer_item::<T, E>(env, &who, &collection_id, &item_id, &dest)?;
+    if status == Status::Success {
+        note_mutation::<T, E>(env, &collection_id)?;
+    }
     trace!(
         target: "runtime",
         "[ChainExtension]|call|transfer"
     );
 
-    Ok(())
+    Ok(status)
 }
 
-impl<T: Config> ChainExtension<T> for Psp02Extension<T>
+/// Transfers `item` to `dest` on behalf of `who` unless one of the checks rejects the
+/// transfer, returning the status to report to the contract.
+fn transfer_item<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    who: &T::AccountId,
+    collection_id: &CollectionIdOf<T>,
+    item_id: &ItemIdOf<T>,
+    dest: &T::AccountId,
+) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let from = T::Backend::owner(collection_id, item_id);
+
+    // Catch the usual contract bugs before they turn into no-ops or stuck items.
+    if dest.as_ref() == &[0u8; 32] {
+        return Ok(Status::TransferToZeroAccount)
+    }
+    if from.as_ref() == Some(dest) {
+        return Ok(Status::TransferToOwner)
+    }
+    if T::ForbidTransfersToSelf::get() && env.ext().address() == dest {
+        return Ok(Status::TransferToSelf)
+    }
+    if Pallet::<T>::is_cooling_down(collection_id, item_id) {
+        return Ok(Status::TransferCooldown)
+    }
+
+    T::Backend::transfer(who, collection_id, item_id, dest)
+        .map_err(convert_err("ChainExtension failed to call transfer"))?;
+    Pallet::<T>::note_transfer(collection_id, item_id);
+    // Transferring an item clears its approvals on every backend.
+    if let Some(from) = from {
+        Pallet::<T>::note_extension_transfer(collection_id, item_id, &from, dest);
+        forget_approvals::<T, E>(env, || {
+            Ok(Pallet::<T>::forget_item(&from, collection_id, item_id))
+        })?;
+    }
+
+    Ok(Status::Success)
+}
+
+/// Burns an item, unless it is no longer owned by the owner the contract expects.
+///
+/// Contracts usually look up the owner before burning, e.g. to pay out or refund them, and
+/// the item can change hands between that lookup and the burn.
+fn burn<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<Status, DispatchError>
 where
-    T: pallet_uniques::Config + pallet_contracts::Config,
+    T: Config,
     <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
 {
-    fn call<E: Ext>(
-        &mut self,
-        env: Environment<E, InitState>,
-    ) -> Result<RetVal, DispatchError>
-    where
-        E: Ext<T = T>,
-        <E::T as SysConfig>::AccountId:
-            UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8; 32]>,
-    {
-        let func_id = FuncId::try_from(env.func_id())?;
-        match func_id {
-            FuncId::Query(func_id) => query::<T, E>(func_id, env)?,
-            FuncId::Transfer => transfer::<T, E>(env)?
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::burn_weight().saturating_add(T::WeightInfo::burn()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|burn / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02BurnInput<ItemIdOf<T>, CollectionIdOf<T>, T::AccountId> = env.read_as()?;
+    let Psp02BurnInput {collection_id, item_id, expected_owner} = input;
+    let who = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
+    }
+    if T::Backend::owner(&collection_id, &item_id).as_ref() != Some(&expected_owner) {
+        return Ok(Status::OwnerMismatch)
+    }
+
+    T::Backend::burn(&who, &collection_id, &item_id)
+        .map_err(convert_err("ChainExtension failed to call burn"))?;
+    forget_approvals::<T, E>(env, || {
+        Ok(Pallet::<T>::forget_burned(&expected_owner, &collection_id, &item_id))
+    })?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|burn"
+    );
+
+    Ok(Status::Success)
+}
+
+/// Transfers items of a collection to their destinations in order, writing the number of
+/// completed transfers and the status of the transfer that stopped the batch to the output.
+///
+/// By default the weight of every transfer is charged upfront, and a transfer rejected by
+/// one of the checks of `transfer` undoes the batch and is reported with its status. In
+/// best-effort mode each transfer is charged on its own, and the batch stops early at the
+/// first transfer the gas left does not cover, one of the checks rejects or the NFT pallet
+/// refuses. The transfers before it are kept and the output carries its status, or
+/// `Success` when the gas ran out, so the contract can look into or resume from there in
+/// another call. A destroyed collection is reported before any transfer. Items are
+/// transferred as the account the contract operates as for `transfer`.
+fn transfer_batch<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let input: Psp02TransferBatchInput<
+        ItemIdOf<T>,
+        CollectionIdOf<T>,
+        T::AccountId,
+        T::MaxBatchLen,
+    > = env.read_as()?;
+    let Psp02TransferBatchInput {collection_id, transfers, best_effort} = input;
+
+    let per_transfer = T::Backend::transfer_weight().saturating_add(T::WeightInfo::transfer());
+    let upfront = if best_effort {
+        Weight::zero()
+    } else {
+        per_transfer.saturating_mul(transfers.len() as u64)
+    };
+    let charged_weight = charge::<T, E>(env, upfront)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|transfer_batch / charge_weight:{:?}",
+        charged_weight
+    );
+    let who = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
+    }
+
+    let (status, completed) = with_transaction(|| {
+        let result = transfer_items::<T, E>(env, &who, &collection_id, transfers, best_effort);
+        match result {
+            Ok((Status::Success, _)) => TransactionOutcome::Commit(result),
+            Ok(_) if best_effort => TransactionOutcome::Commit(result),
+            _ => TransactionOutcome::Rollback(result),
+        }
+    })?;
+    if status != Status::Success && !best_effort {
+        return Ok(status)
+    }
+    if completed > 0 {
+        note_mutation::<T, E>(env, &collection_id)?;
+    }
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|transfer_batch / completed:{:?}",
+        completed
+    );
+
+    write_output::<T, E>(env, &(completed, status as u32).encode())
+        .map_err(convert_err("ChainExtension failed to call transfer_batch"))?;
+    Ok(Status::Success)
+}
+
+/// Transfers `transfers` in order, returning the status to report and the number of
+/// completed transfers.
+///
+/// In best-effort mode every transfer runs in its own transaction, so one the NFT pallet
+/// refuses is undone on its own and reported as `TransferFailed`.
+fn transfer_items<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    who: &T::AccountId,
+    collection_id: &CollectionIdOf<T>,
+    transfers: impl IntoIterator<Item = (ItemIdOf<T>, T::AccountId)>,
+    best_effort: bool,
+) -> Result<(Status, u32), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let per_transfer = T::Backend::transfer_weight().saturating_add(T::WeightInfo::transfer());
+    // `transfer_item` forgets the approvals of the item and the batch bumps the nonce
+    // afterwards, so a transfer needs gas for these too before it is started.
+    let needed = per_transfer
+        .saturating_add(T::WeightInfo::forget_item(T::MaxDelegates::get()))
+        .saturating_add(T::WeightInfo::bump_nonce());
+
+    let mut completed: u32 = 0;
+    for (item_id, dest) in transfers {
+        if best_effort {
+            if env.ext().gas_meter().gas_left().ref_time() < needed.ref_time() {
+                break
+            }
+            env.charge_weight(per_transfer)?;
+        }
+        let status = if best_effort {
+            transfer_item_or_report::<T, E>(env, who, collection_id, &item_id, &dest)?
+        } else {
+            transfer_item::<T, E>(env, who, collection_id, &item_id, &dest)?
+        };
+        if status != Status::Success {
+            return Ok((status, completed))
         }
+        completed += 1;
+    }
+    Ok((Status::Success, completed))
+}
+
+/// Calls `transfer_item` in a transaction of its own, turning an error of the NFT pallet
+/// into `TransferFailed`. Running out of gas is still an error.
+fn transfer_item_or_report<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+    who: &T::AccountId,
+    collection_id: &CollectionIdOf<T>,
+    item_id: &ItemIdOf<T>,
+    dest: &T::AccountId,
+) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    with_transaction(|| match transfer_item::<T, E>(env, who, collection_id, item_id, dest) {
+        Ok(status) => TransactionOutcome::Commit(Ok(status)),
+        Err(err) if err == pallet_contracts::Error::<T>::OutOfGas.into() =>
+            TransactionOutcome::Rollback(Err(err)),
+        Err(err) => {
+            trace!(
+                target: "runtime",
+                "[ChainExtension]|call|transfer_batch / refused:{:?}",
+                err
+            );
+            TransactionOutcome::Rollback(Ok(Status::TransferFailed))
+        },
+    })
+}
+
+fn approve<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::approve_transfer_weight()
+            .saturating_add(T::WeightInfo::approve(T::MaxApprovals::get())),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|approve / charge_weight:{:?}",
+        charged_weight
+    );
 
-        Ok(RetVal::Converging(0))
+    let input: Psp02ApprovalInput<ItemIdOf<T>, CollectionIdOf<T>, T::AccountId> = env.read_as()?;
+    let Psp02ApprovalInput {collection_id, item_id, delegate} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
     }
+
+    T::Backend::approve_transfer(&owner, &collection_id, &item_id, &delegate)
+        .map_err(convert_err("ChainExtension failed to call approve"))?;
+    forget_approvals::<T, E>(env, || {
+        Pallet::<T>::note_approval(&owner, &collection_id, &item_id, &delegate)
+    })?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|approve"
+    );
+
+    Ok(Status::Success)
+}
+
+fn cancel_approval<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::cancel_approval_weight()
+            .saturating_add(T::WeightInfo::cancel_approval(T::MaxApprovals::get())),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_approval / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02ApprovalInput<ItemIdOf<T>, CollectionIdOf<T>, T::AccountId> = env.read_as()?;
+    let Psp02ApprovalInput {collection_id, item_id, delegate} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    T::Backend::cancel_approval(&owner, &collection_id, &item_id, &delegate)
+        .map_err(convert_err("ChainExtension failed to call cancel_approval"))?;
+    Pallet::<T>::forget_approval(&owner, &collection_id, &item_id, &delegate);
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_approval"
+    );
+
+    Ok(())
+}
+
+fn revoke_all_approvals<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let input: Psp02DelegateInput<T::AccountId> = env.read_as()?;
+    let Psp02DelegateInput {delegate} = input;
+    let owner = Pallet::<T>::account_of(env.ext().address())?;
+
+    let approved = Approvals::<T>::decode_len(&owner, &delegate).unwrap_or_default() as u32;
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::cancel_approval_weight()
+            .saturating_mul(approved as u64)
+            .saturating_add(T::WeightInfo::revoke_all_approvals(approved)),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|revoke_all_approvals / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let revoked = Pallet::<T>::revoke_all_approvals(&owner, &delegate)?;
+    let mut collections = Vec::new();
+    for (collection, _) in &revoked {
+        if !collections.contains(collection) {
+            collections.push(*collection);
+        }
+    }
+    for collection in &collections {
+        note_mutation::<T, E>(env, collection)?;
+    }
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|revoke_all_approvals"
+    );
+
+    write_output::<T, E>(env, &revoked.encode())
+        .map_err(convert_err("ChainExtension failed to call revoke_all_approvals"))
+}
+
+fn attest_ownership<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::attest_ownership())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|attest_ownership / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as()?;
+    let Psp02ItemInput {collection_id, item_id} = input;
+
+    let attestation = Pallet::<T>::attest_ownership(&collection_id, &item_id);
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|attest_ownership"
+    );
+
+    write_output::<T, E>(env, &attestation.encode())
+        .map_err(convert_err("ChainExtension failed to call attest_ownership"))
+}
+
+fn set_metadata<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let len = env.in_len();
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::set_metadata_weight().saturating_add(T::WeightInfo::set_metadata(len)),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_metadata / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02MetadataInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as_unbounded(len)?;
+    let Psp02MetadataInput {collection_id, item_id, data} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    T::Backend::set_metadata(&owner, &collection_id, &item_id, data)
+        .map_err(convert_err("ChainExtension failed to call set_metadata"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_metadata"
+    );
+
+    Ok(())
+}
+
+fn clear_metadata<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::Backend::clear_metadata_weight())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|clear_metadata / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as()?;
+    let Psp02ItemInput {collection_id, item_id} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    T::Backend::clear_metadata(&owner, &collection_id, &item_id)
+        .map_err(convert_err("ChainExtension failed to call clear_metadata"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|clear_metadata"
+    );
+
+    Ok(())
+}
+
+fn freeze<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::freeze_weight().saturating_add(T::WeightInfo::freeze()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|freeze / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02FreezeInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as()?;
+    let Psp02FreezeInput {collection_id, item_id, reason} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    T::Backend::freeze(&owner, &collection_id, &item_id)
+        .map_err(convert_err("ChainExtension failed to call freeze"))?;
+    FreezeReasons::<T>::set(collection_id, item_id, reason);
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|freeze"
+    );
+
+    Ok(())
+}
+
+fn thaw<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::thaw_weight().saturating_add(T::WeightInfo::thaw()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|thaw / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02ItemInput<ItemIdOf<T>, CollectionIdOf<T>> = env.read_as()?;
+    let Psp02ItemInput {collection_id, item_id} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    T::Backend::thaw(&owner, &collection_id, &item_id)
+        .map_err(convert_err("ChainExtension failed to call thaw"))?;
+    FreezeReasons::<T>::remove(collection_id, item_id);
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|thaw"
+    );
+
+    Ok(())
+}
+
+fn act_for<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::act_for())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|act_for / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02ActForInput<T::AccountId> = env.read_as()?;
+    let Psp02ActForInput {real} = input;
+    let contract = env.ext().address().clone();
+
+    Pallet::<T>::act_for(&contract, real)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|act_for"
+    );
+
+    Ok(())
+}
+
+fn admin_op<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let input: Psp02AdminOpInput<CollectionIdOf<T>, AdminOpOf<T>> = env.read_as()?;
+    let Psp02AdminOpInput {collection_id, op} = input;
+    let charged_weight = charge::<T, E>(
+        env,
+        Pallet::<T>::admin_op_weight(&collection_id, &op)
+            .saturating_add(T::WeightInfo::admin_op()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|admin_op / charge_weight:{:?}",
+        charged_weight
+    );
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    let executed = Pallet::<T>::propose_admin_op(&owner, &collection_id, op)
+        .map_err(convert_err("ChainExtension failed to call admin_op"))?;
+    if let Some(unused) = executed {
+        env.adjust_weight(charged_weight, charged_weight.amount().saturating_sub(unused));
+    }
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|admin_op"
+    );
+
+    write_output::<T, E>(env, &executed.is_some().encode())
+        .map_err(convert_err("ChainExtension failed to call admin_op"))
+}
+
+fn confirm_admin_op<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+    let Psp02CollectionInput {collection_id} = input;
+    let weight = PendingAdminOps::<T>::get(collection_id)
+        .map(|pending| Pallet::<T>::admin_op_weight(&collection_id, &pending.op))
+        .unwrap_or_default();
+    let charged_weight =
+        charge::<T, E>(env, weight.saturating_add(T::WeightInfo::confirm_admin_op()))?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|confirm_admin_op / charge_weight:{:?}",
+        charged_weight
+    );
+    let confirmer = account_for::<T, E>(env, &collection_id)?;
+
+    let unused = Pallet::<T>::confirm_admin_op(&confirmer, &collection_id)
+        .map_err(convert_err("ChainExtension failed to call confirm_admin_op"))?;
+    env.adjust_weight(charged_weight, charged_weight.amount().saturating_sub(unused));
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|confirm_admin_op"
+    );
+
+    Ok(())
+}
+
+fn cancel_admin_op<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::cancel_admin_op())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_admin_op / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+    let Psp02CollectionInput {collection_id} = input;
+    let who = account_for::<T, E>(env, &collection_id)?;
+
+    Pallet::<T>::cancel_admin_op(&who, &collection_id)
+        .map_err(convert_err("ChainExtension failed to call cancel_admin_op"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_admin_op"
+    );
+
+    Ok(())
+}
+
+fn next_free_item_id<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::WeightInfo::next_free_item_id(T::MaxItemIdScan::get()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|next_free_item_id / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02CollectionInput<CollectionIdOf<T>> = env.read_as()?;
+    let Psp02CollectionInput {collection_id} = input;
+
+    let item_id = Pallet::<T>::next_free_item_id(&collection_id);
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|next_free_item_id"
+    );
+
+    write_output::<T, E>(env, &item_id.encode())
+        .map_err(convert_err("ChainExtension failed to call next_free_item_id"))
+}
+
+fn mint_next<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::mint_weight()
+            .saturating_add(T::WeightInfo::mint_next(T::MaxItemIdScan::get())),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|mint_next / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02MintNextInput<CollectionIdOf<T>, T::AccountId> = env.read_as()?;
+    let Psp02MintNextInput {collection_id, owner} = input;
+    let who = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
+    }
+
+    let item_id = Pallet::<T>::mint_next(&who, &collection_id, &owner)
+        .map_err(convert_err("ChainExtension failed to call mint_next"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|mint_next"
+    );
+
+    write_output::<T, E>(env, &item_id.encode())
+        .map_err(convert_err("ChainExtension failed to call mint_next"))?;
+    Ok(Status::Success)
+}
+
+/// Mints the first unused item id of a collection, anchors a content hash to it and
+/// writes the id to the output.
+fn mint_next_with_provenance<T, E>(
+    env: &mut Environment<E, BufInBufOut>,
+) -> Result<Status, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::Backend::mint_weight()
+            .saturating_add(T::WeightInfo::mint_next(T::MaxItemIdScan::get()))
+            .saturating_add(T::WeightInfo::anchor_provenance()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|mint_next_with_provenance / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02MintNextWithProvenanceInput<CollectionIdOf<T>, T::AccountId, T::Hash> =
+        env.read_as()?;
+    let Psp02MintNextWithProvenanceInput {collection_id, owner, hash} = input;
+    let who = account_for::<T, E>(env, &collection_id)?;
+    if collection_destroyed::<T, E>(env, &collection_id)? {
+        return Ok(Status::CollectionDestroyed)
+    }
+
+    let item_id = Pallet::<T>::mint_next_with_provenance(&who, &collection_id, &owner, hash)
+        .map_err(convert_err("ChainExtension failed to call mint_next_with_provenance"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|mint_next_with_provenance"
+    );
+
+    write_output::<T, E>(env, &item_id.encode())
+        .map_err(convert_err("ChainExtension failed to call mint_next_with_provenance"))?;
+    Ok(Status::Success)
+}
+
+fn grant_capability<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::WeightInfo::grant_capability(T::MaxCapabilities::get()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|grant_capability / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02GrantCapabilityInput<CollectionIdOf<T>, T::AccountId, T::MaxCapabilities> =
+        env.read_as()?;
+    let Psp02GrantCapabilityInput {grantee, collection_id, func_ids} = input;
+    // Capabilities cannot be passed on, so only a contract's own ones or those of a user
+    // it is the proxy of can be granted.
+    let grantor = Pallet::<T>::account_of(env.ext().address())?;
+
+    Pallet::<T>::grant_capability(&grantor, &grantee, &collection_id, &func_ids)
+        .map_err(convert_err("ChainExtension failed to call grant_capability"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|grant_capability"
+    );
+
+    Ok(())
+}
+
+fn set_memo<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::set_memo())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_memo / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02MemoInput<ItemIdOf<T>, CollectionIdOf<T>, T::MaxMemoLen> = env.read_as()?;
+    let Psp02MemoInput {collection_id, item_id, memo} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    Pallet::<T>::set_memo(&owner, &collection_id, &item_id, memo)
+        .map_err(convert_err("ChainExtension failed to call set_memo"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_memo"
+    );
+
+    Ok(())
+}
+
+fn set_transfer_cooldown<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::set_transfer_cooldown())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_transfer_cooldown / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02CooldownInput<CollectionIdOf<T>, T::BlockNumber> = env.read_as()?;
+    let Psp02CooldownInput {collection_id, cooldown} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    Pallet::<T>::set_transfer_cooldown(&owner, &collection_id, cooldown)
+        .map_err(convert_err("ChainExtension failed to call set_transfer_cooldown"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_transfer_cooldown"
+    );
+
+    Ok(())
+}
+
+fn set_watcher<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::set_watcher())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_watcher / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02WatcherInput<CollectionIdOf<T>, T::AccountId> = env.read_as()?;
+    let Psp02WatcherInput {collection_id, watcher} = input;
+    let owner = account_for::<T, E>(env, &collection_id)?;
+
+    Pallet::<T>::set_watcher(&owner, &collection_id, watcher)
+        .map_err(convert_err("ChainExtension failed to call set_watcher"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|set_watcher"
+    );
+
+    Ok(())
+}
+
+/// Queues a deferred operation and writes its id to the output.
+fn submit_deferred_op<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(env, T::WeightInfo::submit_deferred_op())?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|submit_deferred_op / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02AdminOpInput<CollectionIdOf<T>, DeferredOpOf<T>> = env.read_as()?;
+    let Psp02AdminOpInput {collection_id, op} = input;
+    let who = account_for::<T, E>(env, &collection_id)?;
+
+    let op_id = Pallet::<T>::submit_deferred_op(&who, &collection_id, op)
+        .map_err(convert_err("ChainExtension failed to call submit_deferred_op"))?;
+    note_mutation::<T, E>(env, &collection_id)?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|submit_deferred_op"
+    );
+
+    write_output::<T, E>(env, &op_id.encode())
+        .map_err(convert_err("ChainExtension failed to call submit_deferred_op"))
+}
+
+fn cancel_deferred_op<T, E>(env: &mut Environment<E, BufInBufOut>) -> Result<(), DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let charged_weight = charge::<T, E>(
+        env,
+        T::WeightInfo::cancel_deferred_op(T::MaxDeferredOps::get()),
+    )?;
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_deferred_op / charge_weight:{:?}",
+        charged_weight
+    );
+
+    let input: Psp02DeferredOpInput = env.read_as()?;
+    let collection = DeferredOps::<T>::get(input.op_id).map(|queued| queued.collection);
+    // The contract cancels as the account it would submit the operation as.
+    let who = match &collection {
+        Some(collection) => account_for::<T, E>(env, collection)?,
+        None => Pallet::<T>::account_of(env.ext().address())?,
+    };
+
+    Pallet::<T>::cancel_deferred_op(&who, input.op_id)
+        .map_err(convert_err("ChainExtension failed to call cancel_deferred_op"))?;
+    if let Some(collection) = collection {
+        note_mutation::<T, E>(env, &collection)?;
+    }
+    trace!(
+        target: "runtime",
+        "[ChainExtension]|call|cancel_deferred_op"
+    );
+
+    Ok(())
+}
+
+impl<T: Config> ChainExtension<T> for Psp02Extension<T>
+where
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+{
+    fn call<E: Ext>(
+        &mut self,
+        env: Environment<E, InitState>,
+    ) -> Result<RetVal, DispatchError>
+    where
+        E: Ext<T = T>,
+        <E::T as SysConfig>::AccountId:
+            UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    {
+        #[cfg(not(feature = "debug"))]
+        return dispatch::<T, E>(env);
+
+        // Instead of trapping the contract, which would revert the record along with
+        // everything else, failed calls are reverted on their own and reported through a
+        // status code.
+        #[cfg(feature = "debug")]
+        {
+            let mut env = env;
+            let contract = env.ext().address().clone();
+            let func_id = env.func_id();
+            let result = with_transaction(|| {
+                let result = dispatch::<T, E>(env);
+                match result {
+                    Ok(_) => TransactionOutcome::Commit(result),
+                    Err(_) => TransactionOutcome::Rollback(result),
+                }
+            });
+            result.or_else(|error| {
+                LastErrors::<T>::insert(contract, LastError { func_id, error });
+                Ok(RetVal::Converging(Status::Failed as u32))
+            })
+        }
+    }
+}
+
+/// Returns the function ID `func_id` is served under, following `T::LegacyFuncIds`.
+fn current_func_id<T: Config>(func_id: u16) -> u16 {
+    T::LegacyFuncIds::get()
+        .iter()
+        .find(|(legacy, _)| *legacy == func_id)
+        .map_or(func_id, |(_, current)| *current)
+}
+
+fn dispatch<T, E>(env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let func_id = current_func_id::<T>(env.func_id());
+    if custom::CUSTOM_FUNC_IDS.contains(&func_id) {
+        return T::CustomHandler::call(func_id, env)
+    }
+    let mut env = env.buf_in_buf_out();
+    #[cfg(not(feature = "debug"))]
+    return serve::<T, E>(func_id, &mut env);
+
+    // Debug builds note the call and the answer in the debug buffer, for `Replay` on the
+    // contract side. Only dry runs with debug output enabled keep a debug buffer, so nothing
+    // about the call is stored, and the input is read for the note in dry runs only.
+    #[cfg(feature = "debug")]
+    {
+        if env.ext().append_debug_buffer("") {
+            let input = env.read(env.in_len())?;
+            // Noted under the function ID the contract called, which may be a legacy one.
+            let func_id_called = env.func_id().to_be_bytes();
+            debug_note(env.ext(), "call", &[&func_id_called, &input]);
+        }
+        let result = serve::<T, E>(func_id, &mut env);
+        // `call` reports failed calls with `FAILED` in debug builds.
+        let status = match &result {
+            Ok(RetVal::Converging(status)) => *status,
+            _ => status::FAILED,
+        };
+        debug_note(env.ext(), "status", &[&status.to_be_bytes()]);
+        result
+    }
+}
+
+/// Notes `parts` in hex under `label` in the debug buffer, e.g. `psp02 output: 0x2a00`.
+#[cfg(feature = "debug")]
+fn debug_note<E: Ext>(ext: &mut E, label: &str, parts: &[&[u8]]) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    // Appending nothing tells whether there is a debug buffer to note anything in.
+    if !ext.append_debug_buffer("") {
+        return
+    }
+    let mut line = Vec::from(*b"psp02 ");
+    line.extend_from_slice(label.as_bytes());
+    line.push(b':');
+    for part in parts {
+        line.extend_from_slice(b" 0x");
+        for byte in part.iter() {
+            line.push(HEX[usize::from(byte >> 4)]);
+            line.push(HEX[usize::from(byte & 0xf)]);
+        }
+    }
+    line.push(b'\n');
+    ext.append_debug_buffer(core::str::from_utf8(&line).unwrap_or_default());
+}
+
+/// Serves `func_id`, one of the built-in function IDs.
+fn serve<T, E>(
+    func_id: u16,
+    env: &mut Environment<E, BufInBufOut>,
+) -> Result<RetVal, DispatchError>
+where
+    T: Config,
+    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8; 32]>,
+    E: Ext<T = T>,
+{
+    let func_id = FuncId::try_from(func_id)?;
+    match func_id {
+        FuncId::Query(func_id) => query::<T, E>(func_id, env)?,
+        FuncId::Transfer => {
+            let status = transfer::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+        FuncId::TransferBatch => {
+            let status = transfer_batch::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+        FuncId::Approve => {
+            let status = approve::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+        FuncId::CancelApproval => cancel_approval::<T, E>(env)?,
+        FuncId::SetMetadata => set_metadata::<T, E>(env)?,
+        FuncId::ClearMetadata => clear_metadata::<T, E>(env)?,
+        FuncId::RevokeAllApprovals => revoke_all_approvals::<T, E>(env)?,
+        FuncId::AttestOwnership => attest_ownership::<T, E>(env)?,
+        FuncId::Freeze => freeze::<T, E>(env)?,
+        FuncId::Thaw => thaw::<T, E>(env)?,
+        FuncId::ActFor => act_for::<T, E>(env)?,
+        FuncId::AdminOp => admin_op::<T, E>(env)?,
+        FuncId::ConfirmAdminOp => confirm_admin_op::<T, E>(env)?,
+        FuncId::CancelAdminOp => cancel_admin_op::<T, E>(env)?,
+        FuncId::NextFreeItemId => next_free_item_id::<T, E>(env)?,
+        FuncId::MintNext => {
+            let status = mint_next::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+        FuncId::GrantCapability => grant_capability::<T, E>(env)?,
+        FuncId::SetMemo => set_memo::<T, E>(env)?,
+        FuncId::SetTransferCooldown => set_transfer_cooldown::<T, E>(env)?,
+        FuncId::SubmitDeferredOp => submit_deferred_op::<T, E>(env)?,
+        FuncId::CancelDeferredOp => cancel_deferred_op::<T, E>(env)?,
+        FuncId::SetWatcher => set_watcher::<T, E>(env)?,
+        FuncId::MintNextWithProvenance => {
+            let status = mint_next_with_provenance::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+        FuncId::Burn => {
+            let status = burn::<T, E>(env)?;
+            return Ok(RetVal::Converging(status as u32))
+        }
+    }
+
+    Ok(RetVal::Converging(0))
 }
 
 impl<T: Config> RegisteredChainExtension<T> for Psp02Extension<T>
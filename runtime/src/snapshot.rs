@@ -0,0 +1,46 @@
+//! Ownership snapshots published by governance as merkle roots.
+//!
+//! Airdrop contracts often need to know who held a collection at some past block. Rather
+//! than storing the holders on-chain, governance publishes the root of a merkle tree over
+//! them, and claimants present a proof of their leaf.
+//!
+//! The leaf of `account` is the hash of `(collection, block_number, account)`. Inner nodes
+//! are the hash of their two children, the smaller one first, so proofs need no
+//! left/right flags.
+
+use sp_runtime::traits::Hash;
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the leaf of `account` in the snapshot of `collection` at `block_number`.
+    pub fn snapshot_leaf(
+        collection: &CollectionIdOf<T>,
+        block_number: &T::BlockNumber,
+        account: &T::AccountId,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(collection, block_number, account))
+    }
+
+    /// Returns the parent of two nodes of a snapshot.
+    pub fn snapshot_node(a: &T::Hash, b: &T::Hash) -> T::Hash {
+        if a <= b {
+            T::Hashing::hash_of(&(a, b))
+        } else {
+            T::Hashing::hash_of(&(b, a))
+        }
+    }
+
+    /// Returns whether `proof` shows that `account` is part of the snapshot of `collection`
+    /// at `block_number`. Snapshots that were never published contain nobody.
+    pub fn verify_snapshot_membership(
+        collection: &CollectionIdOf<T>,
+        block_number: &T::BlockNumber,
+        account: &T::AccountId,
+        proof: &[T::Hash],
+    ) -> bool {
+        let leaf = Self::snapshot_leaf(collection, block_number, account);
+        let root = proof.iter().fold(leaf, |node, sibling| Self::snapshot_node(&node, sibling));
+        SnapshotRoots::<T>::get(collection, block_number) == Some(root)
+    }
+}
@@ -0,0 +1,46 @@
+//! The status codes the extension returns to contracts.
+//!
+//! Deployed contracts match on these numbers, so they are frozen: a code is never
+//! renumbered or reused, and new codes are appended to [`REGISTRY`]. Codes the runtime
+//! stops returning keep their entry.
+
+/// The call succeeded.
+pub const SUCCESS: u32 = 0;
+/// Reserved for the contract-side `TotalSupplyFailed`; never returned by the runtime.
+pub const TOTAL_SUPPLY_FAILED: u32 = 1;
+/// The destination of a transfer was the all-zero account.
+pub const TRANSFER_TO_ZERO_ACCOUNT: u32 = 2;
+/// The destination of a transfer already owns the item.
+pub const TRANSFER_TO_OWNER: u32 = 3;
+/// The destination of a transfer was the calling contract.
+pub const TRANSFER_TO_SELF: u32 = 4;
+/// The call failed and was reverted. Only returned with the `debug` feature.
+pub const FAILED: u32 = 5;
+/// The item was transferred too recently for the cooldown of its collection.
+pub const TRANSFER_COOLDOWN: u32 = 6;
+/// The item to burn was not owned by the owner the contract expected.
+pub const OWNER_MISMATCH: u32 = 7;
+/// The collection was destroyed, or is queued for destruction.
+pub const COLLECTION_DESTROYED: u32 = 8;
+/// The NFT pallet refused a transfer of a best-effort batch, e.g. because the item is
+/// frozen.
+pub const TRANSFER_FAILED: u32 = 9;
+
+/// Every status code ever assigned, along with its name, in ascending order.
+pub const REGISTRY: &[(u32, &str)] = &[
+    (SUCCESS, "Success"),
+    (TOTAL_SUPPLY_FAILED, "TotalSupplyFailed"),
+    (TRANSFER_TO_ZERO_ACCOUNT, "TransferToZeroAccount"),
+    (TRANSFER_TO_OWNER, "TransferToOwner"),
+    (TRANSFER_TO_SELF, "TransferToSelf"),
+    (FAILED, "Failed"),
+    (TRANSFER_COOLDOWN, "TransferCooldown"),
+    (OWNER_MISMATCH, "OwnerMismatch"),
+    (COLLECTION_DESTROYED, "CollectionDestroyed"),
+    (TRANSFER_FAILED, "TransferFailed"),
+];
+
+/// Returns whether `code` is a registered status code.
+pub fn is_registered(code: u32) -> bool {
+    REGISTRY.iter().any(|(registered, _)| *registered == code)
+}
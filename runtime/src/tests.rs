@@ -0,0 +1,1622 @@
+use codec::Encode;
+use frame_support::{
+    assert_noop,
+    assert_ok,
+    traits::{
+        Currency,
+        Get,
+        Hooks,
+    },
+    weights::Weight,
+};
+use pallet_uniques::WeightInfo;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{
+        BlakeTwo256,
+        Hash,
+    },
+    DispatchError,
+};
+
+use crate::{
+    backend::NftBackend,
+    mock::*,
+    AdminOp,
+    AdminOpOf,
+    DeferredOp,
+    DeferredOpOf,
+    OwnershipAttestation,
+    OwnershipAttestationOf,
+    PendingAdminOp,
+    PendingAdminOpOf,
+    QueuedOp,
+    QueuedOpOf,
+};
+
+const COLLECTION: u32 = 0;
+const ITEM: u32 = 42;
+
+/// Every function ID the extension registers, including the legacy IDs of the mock.
+const FUNC_IDS: [u16; 45] = [
+    0x162d, 0xdb20, 0x1932, 0x9c69, 0xb5e2, 0x3e09, 0x817c, 0x2612, 0x145a, 0xe8c5, 0xce8e,
+    0xa49a, 0x4cb3, 0x0d2d, 0xb65e, 0x5873, 0xe63e, 0xd123, 0xc29f, 0x16fb, 0x32ae, 0xaeed,
+    0xbb93, 0x253d, 0xe253, 0xeaf3, 0xc60a, 0x05a6, 0x813e, 0x1e27, 0x3df4, 0x4790,
+    0xe94b, 0x0001, 0x0002, 0x0003, 0x267a, 0x47b5, 0x9c83, 0x5723, 0x47e5, 0x6b71,
+    0x615c, 0x27cd, 0xdfc4,
+];
+
+/// Deploys the fixture and mints `ITEM` of a collection owned by the fixture to it.
+fn setup() -> AccountId {
+    let contract = deploy_fixture();
+    assert_ok!(Uniques::force_create(RuntimeOrigin::root(), COLLECTION, contract.clone(), false));
+    assert_ok!(Uniques::mint(
+        RuntimeOrigin::signed(contract.clone()),
+        COLLECTION,
+        ITEM,
+        contract.clone()
+    ));
+    contract
+}
+
+#[test]
+fn owner_query_returns_owner() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x162d, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<Option<AccountId>>(), Some(contract.clone()));
+
+        let call = call_extension(&contract, 0x162d, (COLLECTION, ITEM + 1));
+        assert_eq!(call.decode::<Option<AccountId>>(), None);
+    });
+}
+
+#[test]
+fn transfer_moves_item() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, BOB));
+        assert!(call.output().is_empty());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+    });
+}
+
+#[test]
+fn transfer_by_non_owner_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&other, 0xdb20, (COLLECTION, ITEM, BOB));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call transfer"))
+        );
+        let call = call_extension(&other, 0x3df4, (COLLECTION, vec![(ITEM, BOB)], false));
+        assert!(call.result.is_err());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+
+        // Approved delegates transfer on behalf of the owner.
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, &other)).result.is_ok());
+        let call = call_extension(&other, 0xdb20, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((crate::status::SUCCESS, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+    });
+}
+
+#[test]
+fn transfer_of_unknown_item_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM + 1, BOB));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call transfer"))
+        );
+    });
+}
+
+#[test]
+fn transfer_charges_weight() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let query = call_extension(&contract, 0x162d, (COLLECTION, ITEM));
+        let transfer = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, BOB));
+        assert!(transfer.result.is_ok());
+
+        let transfer_weight = <() as WeightInfo>::transfer();
+        assert!(
+            transfer.gas_consumed.ref_time() >=
+                query.gas_consumed.ref_time() + transfer_weight.ref_time()
+        );
+    });
+}
+
+#[test]
+fn approve_lets_delegate_transfer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB));
+        assert!(call.result.is_ok());
+        assert_ok!(Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, ITEM, CHARLIE));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(CHARLIE));
+    });
+}
+
+#[test]
+fn approve_requires_permission() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), COLLECTION + 1, ALICE, false));
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(ALICE), COLLECTION + 1, ITEM, BOB));
+
+        let call = call_extension(&contract, 0x1932, (COLLECTION + 1, ITEM, CHARLIE));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call approve"))
+        );
+    });
+}
+
+#[test]
+fn cancel_approval_revokes_delegate() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0x9c69, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert_noop!(
+            Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, ITEM, CHARLIE),
+            pallet_uniques::Error::<Test>::NoPermission
+        );
+    });
+}
+
+#[test]
+fn cancel_approval_of_unknown_delegate_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x9c69, (COLLECTION, ITEM, BOB));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call cancel_approval"))
+        );
+    });
+}
+
+#[test]
+fn approvals_to_lists_items_still_owned() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM + 1,
+            contract.clone()
+        ));
+
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM + 1, BOB)).result.is_ok());
+        let call = call_extension(&contract, 0x817c, BOB);
+        assert_eq!(
+            call.decode::<Vec<(u32, u32)>>(),
+            vec![(COLLECTION, ITEM), (COLLECTION, ITEM + 1)]
+        );
+
+        // Approving another delegate replaces BOB on pallet-uniques.
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, CHARLIE)).result.is_ok());
+        let call = call_extension(&contract, 0x817c, BOB);
+        assert_eq!(call.decode::<Vec<(u32, u32)>>(), vec![(COLLECTION, ITEM + 1)]);
+
+        assert_ok!(Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, ITEM + 1, CHARLIE));
+        let call = call_extension(&contract, 0x817c, BOB);
+        assert!(call.decode::<Vec<(u32, u32)>>().is_empty());
+    });
+}
+
+#[test]
+fn revoke_all_approvals_cancels_every_approval() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM + 1,
+            contract.clone()
+        ));
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM + 1, BOB)).result.is_ok());
+
+        let call = call_extension(&contract, 0x2612, BOB);
+        assert_eq!(
+            call.decode::<Vec<(u32, u32)>>(),
+            vec![(COLLECTION, ITEM), (COLLECTION, ITEM + 1)]
+        );
+        assert_noop!(
+            Uniques::transfer(RuntimeOrigin::signed(BOB), COLLECTION, ITEM, CHARLIE),
+            pallet_uniques::Error::<Test>::NoPermission
+        );
+        assert!(call_extension(&contract, 0x817c, BOB).decode::<Vec<(u32, u32)>>().is_empty());
+    });
+}
+
+#[test]
+fn revoke_all_approvals_skips_approvals_cancelled_outside_the_extension() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM + 1,
+            contract.clone()
+        ));
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM + 1, BOB)).result.is_ok());
+        assert_ok!(Uniques::cancel_approval(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM,
+            Some(BOB)
+        ));
+
+        let call = call_extension(&contract, 0x2612, BOB);
+        assert_eq!(call.decode::<Vec<(u32, u32)>>(), vec![(COLLECTION, ITEM + 1)]);
+        assert!(call_extension(&contract, 0x817c, BOB).decode::<Vec<(u32, u32)>>().is_empty());
+    });
+}
+
+#[test]
+fn approve_beyond_max_approvals_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        for item in 0..5 {
+            if item != ITEM {
+                assert_ok!(Uniques::mint(
+                    RuntimeOrigin::signed(contract.clone()),
+                    COLLECTION,
+                    item,
+                    contract.clone()
+                ));
+            }
+        }
+        for item in 0..4 {
+            assert!(call_extension(&contract, 0x1932, (COLLECTION, item, BOB)).result.is_ok());
+        }
+
+        let call = call_extension(&contract, 0x1932, (COLLECTION, 4, BOB));
+        assert_eq!(call.result, Err(crate::Error::<Test>::TooManyApprovals.into()));
+    });
+}
+
+#[test]
+fn approve_beyond_max_delegates_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 5);
+        let delegate = |i: u8| AccountId::new([10 + i; 32]);
+        for item in 0..4 {
+            let input = (COLLECTION, item, delegate(item as u8));
+            assert!(call_extension(&contract, 0x1932, input).result.is_ok());
+        }
+
+        let call = call_extension(&contract, 0x1932, (COLLECTION, 4, delegate(4)));
+        assert_eq!(call.result, Err(crate::Error::<Test>::TooManyDelegates.into()));
+
+        // Cancelling the only approval to a delegate frees its slot.
+        assert!(call_extension(&contract, 0x9c69, (COLLECTION, 0, delegate(0))).result.is_ok());
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, 4, delegate(4))).result.is_ok());
+        assert_eq!(crate::DelegateCounts::<Test>::get(&contract), 4);
+    });
+}
+
+#[test]
+fn attestation_is_verifiable() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x145a, (COLLECTION, ITEM));
+        let (attestation, commitment) =
+            call.decode::<Option<(OwnershipAttestationOf<Test>, H256)>>().unwrap();
+        assert_eq!(
+            attestation,
+            OwnershipAttestation {
+                block_number: 1,
+                collection: COLLECTION,
+                item: ITEM,
+                owner: contract.clone()
+            }
+        );
+        assert_eq!(commitment, BlakeTwo256::hash_of(&attestation));
+
+        // The attestation stays valid after the item changed hands...
+        System::set_block_number(2);
+        assert!(call_extension(&contract, 0xdb20, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0xe8c5, &attestation).decode::<bool>());
+
+        // ...but cannot be forged for another owner or height.
+        let forged = OwnershipAttestation { owner: BOB, ..attestation.clone() };
+        assert!(!call_extension(&contract, 0xe8c5, &forged).decode::<bool>());
+        let forged = OwnershipAttestation { block_number: 2, ..attestation };
+        assert!(!call_extension(&contract, 0xe8c5, &forged).decode::<bool>());
+
+        let call = call_extension(&contract, 0x145a, (COLLECTION, ITEM + 1));
+        assert_eq!(call.decode::<Option<(OwnershipAttestationOf<Test>, H256)>>(), None);
+    });
+}
+
+#[test]
+fn freeze_records_reason_until_thawed() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let status = || call_extension(&contract, 0x4cb3, (COLLECTION, ITEM));
+
+        assert_eq!(status().decode::<(bool, Option<u32>)>(), (false, None));
+
+        let call = call_extension(&contract, 0xce8e, (COLLECTION, ITEM, Some(7u32)));
+        assert!(call.result.is_ok());
+        assert_eq!(status().decode::<(bool, Option<u32>)>(), (true, Some(7)));
+        assert_noop!(
+            Uniques::transfer(RuntimeOrigin::signed(contract.clone()), COLLECTION, ITEM, BOB),
+            pallet_uniques::Error::<Test>::Frozen
+        );
+
+        assert!(call_extension(&contract, 0xa49a, (COLLECTION, ITEM)).result.is_ok());
+        assert_eq!(status().decode::<(bool, Option<u32>)>(), (false, None));
+        assert_eq!(crate::FreezeReasons::<Test>::get(COLLECTION, ITEM), None);
+    });
+}
+
+#[test]
+fn item_frozen_outside_the_extension_has_no_reason() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::freeze(RuntimeOrigin::signed(contract.clone()), COLLECTION, ITEM));
+
+        let call = call_extension(&contract, 0x4cb3, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<(bool, Option<u32>)>(), (true, None));
+    });
+}
+
+#[test]
+fn freeze_requires_freezer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), COLLECTION + 1, ALICE, false));
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(ALICE),
+            COLLECTION + 1,
+            ITEM,
+            contract.clone()
+        ));
+
+        let call = call_extension(&contract, 0xce8e, (COLLECTION + 1, ITEM, None::<u32>));
+        assert_eq!(call.result, Err(DispatchError::Other("ChainExtension failed to call freeze")));
+        assert_eq!(crate::FreezeReasons::<Test>::get(COLLECTION + 1, ITEM), None);
+    });
+}
+
+#[test]
+fn act_for_requires_proxy() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x0d2d, Some(BOB));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+
+        TestProxies::add(BOB, contract.clone());
+        assert!(call_extension(&contract, 0x0d2d, Some(BOB)).result.is_ok());
+        let call = call_extension(&contract, 0xb65e, ());
+        assert_eq!(call.decode::<Option<AccountId>>(), Some(BOB));
+
+        assert!(call_extension(&contract, 0x0d2d, None::<AccountId>).result.is_ok());
+        let call = call_extension(&contract, 0xb65e, ());
+        assert_eq!(call.decode::<Option<AccountId>>(), None);
+    });
+}
+
+#[test]
+fn proxy_contract_manages_user_items() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), COLLECTION + 1, ALICE, false));
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(ALICE), COLLECTION + 1, ITEM, BOB));
+        TestProxies::add(BOB, contract.clone());
+        assert!(call_extension(&contract, 0x0d2d, Some(BOB)).result.is_ok());
+
+        assert!(call_extension(&contract, 0x1932, (COLLECTION + 1, ITEM, CHARLIE)).result.is_ok());
+        assert_eq!(
+            call_extension(&contract, 0x817c, CHARLIE).decode::<Vec<(u32, u32)>>(),
+            vec![(COLLECTION + 1, ITEM)]
+        );
+        assert_eq!(Uniques::owner(COLLECTION + 1, ITEM), Some(BOB));
+
+        // Once the proxy is removed the contract can no longer act for BOB.
+        TestProxies::remove(&BOB, &contract);
+        let call = call_extension(&contract, 0x9c69, (COLLECTION + 1, ITEM, CHARLIE));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+    });
+}
+
+#[test]
+fn admin_op_without_confirmer_executes_immediately() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::transfer(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM,
+            BOB
+        ));
+
+        let op: AdminOpOf<Test> = AdminOp::ForceTransfer { item: ITEM, dest: CHARLIE };
+        let call = call_extension(&contract, 0x5873, (COLLECTION, op));
+        assert!(call.decode::<bool>());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(CHARLIE));
+    });
+}
+
+#[test]
+fn admin_op_waits_for_confirmer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let confirmer = deploy_fixture_with_salt(vec![1]);
+        let op: AdminOpOf<Test> = AdminOp::SetConfirmer(Some(confirmer.clone()));
+        assert!(call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+        let call = call_extension(&contract, 0x16fb, COLLECTION);
+        assert_eq!(call.decode::<Option<AccountId>>(), Some(confirmer.clone()));
+
+        let call = call_extension(&contract, 0x5873, (COLLECTION, AdminOpOf::<Test>::Destroy));
+        assert!(!call.decode::<bool>());
+        let call = call_extension(&contract, 0xc29f, COLLECTION);
+        assert_eq!(
+            call.decode::<Option<PendingAdminOpOf<Test>>>(),
+            Some(PendingAdminOp {
+                op: AdminOp::Destroy,
+                proposer: contract.clone(),
+                expires_at: 11
+            })
+        );
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+
+        // The proposer cannot confirm its own operation.
+        let call = call_extension(&contract, 0xe63e, COLLECTION);
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call confirm_admin_op"))
+        );
+
+        assert!(call_extension(&confirmer, 0xe63e, COLLECTION).result.is_ok());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), None);
+        assert_eq!(crate::AdminConfirmers::<Test>::get(COLLECTION), None);
+        assert_eq!(crate::PendingAdminOps::<Test>::get(COLLECTION), None);
+    });
+}
+
+#[test]
+fn forced_transfers_are_the_owners_only_way_to_move_foreign_items() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let confirmer = deploy_fixture_with_salt(vec![1]);
+        assert!(call_extension(&contract, 0xdb20, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert_ok!(Uniques::set_team(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            contract.clone(),
+            CHARLIE,
+            contract.clone()
+        ));
+        let op: AdminOpOf<Test> = AdminOp::SetConfirmer(Some(confirmer.clone()));
+        assert!(call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+
+        // The owner is no longer the admin, so it cannot take the item back by itself.
+        assert!(call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &contract)).result.is_err());
+        let op: AdminOpOf<Test> = AdminOp::ForceTransfer { item: ITEM, dest: contract.clone() };
+        assert!(!call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+
+        assert!(call_extension(&confirmer, 0xe63e, COLLECTION).result.is_ok());
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+    });
+}
+
+#[test]
+fn admin_op_expires() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let confirmer = deploy_fixture_with_salt(vec![1]);
+        let op: AdminOpOf<Test> = AdminOp::SetConfirmer(Some(confirmer.clone()));
+        assert!(call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+        let op: AdminOpOf<Test> = AdminOp::ForceTransfer { item: ITEM, dest: BOB };
+        assert!(!call_extension(&contract, 0x5873, (COLLECTION, op.clone())).decode::<bool>());
+
+        System::set_block_number(12);
+        let call = call_extension(&confirmer, 0xe63e, COLLECTION);
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call confirm_admin_op"))
+        );
+
+        // An expired operation can be replaced, and a pending one cancelled.
+        assert!(!call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+        assert!(call_extension(&confirmer, 0xd123, COLLECTION).result.is_ok());
+        assert_eq!(crate::PendingAdminOps::<Test>::get(COLLECTION), None);
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+    });
+}
+
+#[test]
+fn owner_cannot_be_confirmer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let op: AdminOpOf<Test> = AdminOp::SetConfirmer(Some(contract.clone()));
+        let call = call_extension(&contract, 0x5873, (COLLECTION, op));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call admin_op"))
+        );
+    });
+}
+
+#[test]
+fn set_and_clear_metadata() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let reserved = Balances::reserved_balance(&contract);
+        let data = b"ipfs://pet".to_vec();
+
+        let call = call_extension(&contract, 0xb5e2, (COLLECTION, ITEM, data.clone()));
+        assert!(call.result.is_ok());
+        // MetadataDepositBase + DepositPerByte * len
+        assert_eq!(Balances::reserved_balance(&contract), reserved + 1 + data.len() as u64);
+
+        let call = call_extension(&contract, 0x3e09, (COLLECTION, ITEM));
+        assert!(call.result.is_ok());
+        assert_eq!(Balances::reserved_balance(&contract), reserved);
+    });
+}
+
+#[test]
+fn set_metadata_exceeding_string_limit_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0xb5e2, (COLLECTION, ITEM, vec![0u8; 51]));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call set_metadata"))
+        );
+    });
+}
+
+#[test]
+fn clear_metadata_without_metadata_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x3e09, (COLLECTION, ITEM));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call clear_metadata"))
+        );
+    });
+}
+
+#[test]
+fn next_free_item_id_skips_items_minted_outside_the_extension() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x32ae, COLLECTION);
+        assert_eq!(call.decode::<Option<u32>>(), Some(0));
+        // Asking again before minting returns the same id.
+        let call = call_extension(&contract, 0x32ae, COLLECTION);
+        assert_eq!(call.decode::<Option<u32>>(), Some(0));
+
+        for item in 0..2 {
+            assert_ok!(Uniques::mint(
+                RuntimeOrigin::signed(contract.clone()),
+                COLLECTION,
+                item,
+                BOB
+            ));
+        }
+        let call = call_extension(&contract, 0x32ae, COLLECTION);
+        assert_eq!(call.decode::<Option<u32>>(), Some(2));
+        assert_eq!(crate::NextItemIds::<Test>::get(COLLECTION), Some(2));
+    });
+}
+
+#[test]
+fn next_free_item_id_resumes_after_giving_up() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        for item in 0..6 {
+            assert_ok!(Uniques::mint(
+                RuntimeOrigin::signed(contract.clone()),
+                COLLECTION,
+                item,
+                BOB
+            ));
+        }
+
+        // `MaxItemIdScan` is 4, so the first call gives up after ids 0 to 3...
+        let call = call_extension(&contract, 0x32ae, COLLECTION);
+        assert_eq!(call.decode::<Option<u32>>(), None);
+        // ...and the second one continues from there.
+        let call = call_extension(&contract, 0x32ae, COLLECTION);
+        assert_eq!(call.decode::<Option<u32>>(), Some(6));
+    });
+}
+
+#[test]
+fn next_free_item_id_of_unknown_collection_is_none() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x32ae, COLLECTION + 1);
+        assert_eq!(call.decode::<Option<u32>>(), None);
+        assert_eq!(crate::NextItemIds::<Test>::get(COLLECTION + 1), None);
+    });
+}
+
+#[test]
+fn mint_next_assigns_sequential_ids() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(contract.clone()), COLLECTION, 1, BOB));
+
+        let call = call_extension(&contract, 0xaeed, (COLLECTION, BOB));
+        assert_eq!(call.decode::<u32>(), 0);
+        // Ids minted through extrinsics are skipped.
+        let call = call_extension(&contract, 0xaeed, (COLLECTION, CHARLIE));
+        assert_eq!(call.decode::<u32>(), 2);
+
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 2), Some(CHARLIE));
+        assert_eq!(crate::NextItemIds::<Test>::get(COLLECTION), Some(3));
+    });
+}
+
+#[test]
+fn mint_next_requires_issuer() {
+    new_test_ext().execute_with(|| {
+        setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&other, 0xaeed, (COLLECTION, BOB));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call mint_next"))
+        );
+        assert_eq!(Uniques::owner(COLLECTION, 0), None);
+    });
+}
+
+#[test]
+fn collections_owned_pages_through_owned_collections() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        for collection in 1..3 {
+            assert_ok!(Uniques::force_create(
+                RuntimeOrigin::root(),
+                collection,
+                contract.clone(),
+                false
+            ));
+        }
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 3, BOB, false));
+
+        // Pages are capped at `MaxPageLen`, which is 2.
+        let call = call_extension(&contract, 0xbb93, (&contract, None::<u32>, 10u32));
+        let mut owned = call.decode::<Vec<u32>>();
+        assert_eq!(owned.len(), 2);
+        let call = call_extension(&contract, 0xbb93, (&contract, owned.last(), 2u32));
+        owned.extend(call.decode::<Vec<u32>>());
+        let call = call_extension(&contract, 0xbb93, (&contract, owned.last(), 2u32));
+        assert_eq!(call.decode::<Vec<u32>>(), vec![]);
+
+        owned.sort();
+        assert_eq!(owned, vec![0, 1, 2]);
+        let call = call_extension(&contract, 0xbb93, (BOB, None::<u32>, 2u32));
+        assert_eq!(call.decode::<Vec<u32>>(), vec![3]);
+    });
+}
+
+#[test]
+fn capability_scopes_calls_to_granted_func_ids_and_collections() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::force_create(
+            RuntimeOrigin::root(),
+            COLLECTION + 1,
+            contract.clone(),
+            false
+        ));
+        assert_ok!(Uniques::mint(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION + 1,
+            ITEM,
+            contract.clone()
+        ));
+        let grantee = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&grantee, 0x0d2d, Some(&contract));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+
+        // The contract lets the grantee freeze items of `COLLECTION`, and nothing else.
+        let call = call_extension(&contract, 0x253d, (&grantee, COLLECTION, vec![0xce8eu16]));
+        assert!(call.result.is_ok());
+        let call = call_extension(&contract, 0xe253, (&contract, &grantee));
+        assert_eq!(call.decode::<Vec<(u32, u16)>>(), vec![(COLLECTION, 0xce8e)]);
+        assert!(call_extension(&grantee, 0x0d2d, Some(&contract)).result.is_ok());
+
+        let call = call_extension(&grantee, 0xce8e, (COLLECTION, ITEM, None::<u32>));
+        assert!(call.result.is_ok());
+        let call = call_extension(&contract, 0x4cb3, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<(bool, Option<u32>)>(), (true, None));
+        let call = call_extension(&grantee, 0xa49a, (COLLECTION, ITEM));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+        let call = call_extension(&grantee, 0xce8e, (COLLECTION + 1, ITEM, None::<u32>));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+
+        // Transfers are only made as the grantor with the capability to call `transfer`,
+        // which also covers batches.
+        let origin = RuntimeOrigin::signed(contract.clone());
+        assert_ok!(Uniques::mint(origin, COLLECTION, 1, contract.clone()));
+        let call = call_extension(&grantee, 0xdb20, (COLLECTION, 1u32, BOB));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+        let call = call_extension(&grantee, 0x3df4, (COLLECTION, vec![(1u32, BOB)], false));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(contract.clone()));
+        let func_ids = vec![0xce8eu16, 0xdb20];
+        assert!(call_extension(&contract, 0x253d, (&grantee, COLLECTION, func_ids)).result.is_ok());
+        let call = call_extension(&grantee, 0x3df4, (COLLECTION, vec![(1u32, BOB)], false));
+        assert_eq!(call.decode::<(u32, u32)>(), (1, crate::status::SUCCESS));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(BOB));
+        let call = call_extension(&grantee, 0xdb20, (COLLECTION + 1, ITEM, BOB));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+
+        // Granting no func ids withdraws the capability.
+        let call = call_extension(&contract, 0x253d, (&grantee, COLLECTION, Vec::<u16>::new()));
+        assert!(call.result.is_ok());
+        assert!(!crate::Capabilities::<Test>::contains_key(&contract, &grantee));
+        let call = call_extension(&contract, 0xe253, (&contract, &grantee));
+        assert_eq!(call.decode::<Vec<(u32, u16)>>(), vec![]);
+        let call = call_extension(&grantee, 0xce8e, (COLLECTION, ITEM, None::<u32>));
+        assert_eq!(call.result, Err(crate::Error::<Test>::NotProxy.into()));
+    });
+}
+
+#[test]
+fn capabilities_are_bounded() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0x253d, (BOB, COLLECTION, vec![1u16, 2, 3, 4]));
+        assert!(call.result.is_ok());
+        let call = call_extension(&contract, 0x253d, (BOB, COLLECTION + 1, vec![1u16]));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call grant_capability"))
+        );
+    });
+}
+
+#[test]
+fn legacy_func_ids_are_served_as_current_ones() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let grantee = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&contract, 0x0001, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<Option<AccountId>>(), Some(contract.clone()));
+
+        // A capability for the current ID covers calls through the legacy one.
+        let call = call_extension(&contract, 0x253d, (&grantee, COLLECTION, vec![0xce8eu16]));
+        assert!(call.result.is_ok());
+        assert!(call_extension(&grantee, 0x0d2d, Some(&contract)).result.is_ok());
+        let call = call_extension(&grantee, 0x0003, (COLLECTION, ITEM, None::<u32>));
+        assert!(call.result.is_ok());
+        let call = call_extension(&contract, 0x4cb3, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<(bool, Option<u32>)>(), (true, None));
+
+        let call = call_extension(&contract, 0x0002, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((0, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+    });
+}
+
+#[test]
+fn transfer_to_suspicious_destinations_returns_status_codes() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, AccountId::new([0; 32])));
+        assert_eq!(call.result, Ok((2, vec![])));
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &contract));
+        assert_eq!(call.result, Ok((3, vec![])));
+
+        // Transfers to the calling contract are forbidden in the mock runtime.
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(contract.clone()), COLLECTION, 1, BOB));
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, 1, &contract));
+        assert_eq!(call.result, Ok((4, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(BOB));
+    });
+}
+
+#[test]
+fn status_codes_are_frozen() {
+    // Deployed contracts match on these numbers. Extend this table, never change it.
+    let frozen: &[(u32, &str)] = &[
+        (0, "Success"),
+        (1, "TotalSupplyFailed"),
+        (2, "TransferToZeroAccount"),
+        (3, "TransferToOwner"),
+        (4, "TransferToSelf"),
+        (5, "Failed"),
+        (6, "TransferCooldown"),
+        (7, "OwnerMismatch"),
+        (8, "CollectionDestroyed"),
+        (9, "TransferFailed"),
+    ];
+    assert_eq!(crate::status::REGISTRY, frozen);
+}
+
+#[test]
+fn every_status_code_path_returns_its_registered_code() {
+    use crate::status::*;
+
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(contract.clone()), COLLECTION, 1, BOB));
+        assert!(call_extension(&contract, 0x813e, (COLLECTION, Some(10u64))).result.is_ok());
+
+        // `FAILED` is covered by the tests of the `debug` feature, `TRANSFER_FAILED` by
+        // the tests of best-effort batches.
+        let zero = AccountId::new([0; 32]);
+        let matrix = [
+            ((COLLECTION, ITEM, &zero).encode(), TRANSFER_TO_ZERO_ACCOUNT),
+            ((COLLECTION, ITEM, &contract).encode(), TRANSFER_TO_OWNER),
+            ((COLLECTION, 1u32, &contract).encode(), TRANSFER_TO_SELF),
+            ((COLLECTION, ITEM, &other).encode(), SUCCESS),
+            ((COLLECTION, ITEM, &BOB).encode(), TRANSFER_COOLDOWN),
+        ];
+        for (input, expected) in matrix {
+            assert!(is_registered(expected));
+            let call = call_extension_raw(&contract, 0xdb20, &input);
+            assert_eq!(call.result, Ok((expected, vec![])));
+        }
+        assert!(is_registered(OWNER_MISMATCH));
+        let call = call_extension(&contract, 0x6b71, (COLLECTION, 1u32, &contract));
+        assert_eq!(call.result, Ok((OWNER_MISMATCH, vec![])));
+        assert!(is_registered(COLLECTION_DESTROYED));
+        crate::Destructions::<Test>::insert(COLLECTION, crate::Destruction::Queued);
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &BOB));
+        assert_eq!(call.result, Ok((COLLECTION_DESTROYED, vec![])));
+    });
+}
+
+#[test]
+fn snapshot_membership_is_verifiable() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let leaf = |account: &AccountId| ContractsUniques::snapshot_leaf(&COLLECTION, &1, account);
+        let (alice, bob, charlie) = (leaf(&ALICE), leaf(&BOB), leaf(&CHARLIE));
+        let alice_bob = ContractsUniques::snapshot_node(&alice, &bob);
+        let root = ContractsUniques::snapshot_node(&alice_bob, &charlie);
+
+        assert_noop!(
+            ContractsUniques::publish_snapshot_root(
+                RuntimeOrigin::signed(ALICE),
+                COLLECTION,
+                1,
+                Some(root)
+            ),
+            DispatchError::BadOrigin
+        );
+        let query = (COLLECTION, 1u64, BOB, vec![alice, charlie]);
+        assert!(!call_extension(&contract, 0xeaf3, &query).decode::<bool>());
+
+        assert_ok!(ContractsUniques::publish_snapshot_root(
+            RuntimeOrigin::root(),
+            COLLECTION,
+            1,
+            Some(root)
+        ));
+        assert!(call_extension(&contract, 0xeaf3, &query).decode::<bool>());
+        let call = call_extension(&contract, 0xeaf3, (COLLECTION, 1u64, CHARLIE, vec![alice_bob]));
+        assert!(call.decode::<bool>());
+
+        // Proofs only hold for their own leaf and snapshot.
+        let proof = vec![alice, charlie];
+        let call = call_extension(&contract, 0xeaf3, (COLLECTION, 1u64, ALICE, &proof));
+        assert!(!call.decode::<bool>());
+        let call = call_extension(&contract, 0xeaf3, (COLLECTION, 2u64, BOB, &proof));
+        assert!(!call.decode::<bool>());
+
+        assert_ok!(ContractsUniques::publish_snapshot_root(
+            RuntimeOrigin::root(),
+            COLLECTION,
+            1,
+            None
+        ));
+        assert!(!call_extension(&contract, 0xeaf3, &query).decode::<bool>());
+    });
+}
+
+#[test]
+fn owner_writes_memo() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let rex = (COLLECTION, ITEM, b"Rex".to_vec());
+        assert!(call_extension(&contract, 0xc60a, &rex).result.is_ok());
+        let call = call_extension(&other, 0x05a6, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<Option<Vec<u8>>>(), Some(b"Rex".to_vec()));
+
+        let call = call_extension(&other, 0xc60a, (COLLECTION, ITEM, b"Max".to_vec()));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call set_memo"))
+        );
+        // Memos are bounded by `MaxMemoLen`.
+        let call = call_extension(&contract, 0xc60a, (COLLECTION, ITEM, vec![0u8; 17]));
+        assert!(call.result.is_err());
+
+        let empty = (COLLECTION, ITEM, Vec::<u8>::new());
+        assert!(call_extension(&contract, 0xc60a, empty).result.is_ok());
+        assert_eq!(crate::Memos::<Test>::get(COLLECTION, ITEM), None);
+    });
+}
+
+#[test]
+fn memo_is_hidden_from_next_owner() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let rex = (COLLECTION, ITEM, b"Rex".to_vec());
+        assert!(call_extension(&contract, 0xc60a, rex).result.is_ok());
+
+        let origin = RuntimeOrigin::signed(contract.clone());
+        assert_ok!(Uniques::transfer(origin, COLLECTION, ITEM, BOB));
+        let call = call_extension(&contract, 0x05a6, (COLLECTION, ITEM));
+        assert_eq!(call.decode::<Option<Vec<u8>>>(), None);
+    });
+}
+
+#[test]
+fn transfer_cooldown_delays_next_transfer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&other, 0x813e, (COLLECTION, Some(10u64)));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call set_transfer_cooldown"))
+        );
+        assert!(call_extension(&contract, 0x813e, (COLLECTION, Some(10u64))).result.is_ok());
+        let call = call_extension(&contract, 0x1e27, COLLECTION);
+        assert_eq!(call.decode::<Option<u64>>(), Some(10));
+
+        // The first transfer starts the cooldown, which blocks the item from moving on.
+        System::set_block_number(5);
+        assert!(call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &other)).result.is_ok());
+        System::set_block_number(14);
+        let call = call_extension(&other, 0xdb20, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((6, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(other.clone()));
+
+        System::set_block_number(15);
+        assert_eq!(call_extension(&other, 0xdb20, (COLLECTION, ITEM, BOB)).result, Ok((0, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+    });
+}
+
+#[test]
+fn free_holding_reports_how_the_collection_was_created() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        assert_ok!(Uniques::force_create(RuntimeOrigin::root(), 1, contract.clone(), true));
+
+        let call = call_extension(&contract, 0xe94b, COLLECTION);
+        assert_eq!(call.decode::<Option<bool>>(), Some(false));
+        assert_eq!(call_extension(&contract, 0xe94b, 1u32).decode::<Option<bool>>(), Some(true));
+        assert_eq!(call_extension(&contract, 0xe94b, 2u32).decode::<Option<bool>>(), None);
+    });
+}
+
+/// Returns the weight `on_idle` needs to execute `n` steps of deferred mints.
+fn deferred_mint_steps(n: u64) -> Weight {
+    let scan = <<Test as crate::Config>::MaxItemIdScan as Get<u32>>::get();
+    <Test as crate::Config>::Backend::mint_weight()
+        .saturating_add(<() as crate::WeightInfo>::mint_next(scan))
+        .saturating_add(<() as crate::WeightInfo>::collection_destroyed())
+        .saturating_add(<() as crate::WeightInfo>::process_deferred_op())
+        .saturating_mul(n)
+}
+
+#[test]
+fn deferred_mints_take_turns_in_on_idle() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let mint = |count| DeferredOpOf::<Test>::MintBatch { owner: BOB, count };
+        assert_eq!(call_extension(&contract, 0x267a, (COLLECTION, mint(3))).decode::<u32>(), 0);
+        assert_eq!(call_extension(&contract, 0x267a, (COLLECTION, mint(1))).decode::<u32>(), 1);
+
+        ContractsUniques::on_idle(1, deferred_mint_steps(2));
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(BOB));
+        let call = call_extension(&contract, 0x47b5, 0u32);
+        assert_eq!(
+            call.decode::<Option<QueuedOpOf<Test>>>(),
+            Some(QueuedOp { op: mint(2), collection: COLLECTION, submitter: contract.clone() })
+        );
+        let call = call_extension(&contract, 0x47b5, 1u32);
+        assert_eq!(call.decode::<Option<QueuedOpOf<Test>>>(), None);
+
+        let short_of_a_step = deferred_mint_steps(1).saturating_sub(Weight::from_ref_time(1));
+        ContractsUniques::on_idle(2, short_of_a_step);
+        assert_eq!(Uniques::owner(COLLECTION, 2), None);
+        ContractsUniques::on_idle(3, deferred_mint_steps(10));
+        assert_eq!(Uniques::owner(COLLECTION, 3), Some(BOB));
+        let call = call_extension(&contract, 0x47b5, 0u32);
+        assert_eq!(call.decode::<Option<QueuedOpOf<Test>>>(), None);
+        assert!(crate::DeferredQueue::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn deferred_destroy_requires_owner_without_confirmer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&other, 0x267a, (COLLECTION, DeferredOpOf::<Test>::Destroy));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call submit_deferred_op"))
+        );
+        let call = call_extension(&contract, 0x267a, (COLLECTION, DeferredOpOf::<Test>::Destroy));
+        assert_eq!(call.decode::<u32>(), 0);
+
+        // The confirmer set in the meantime keeps the queued operation from destroying.
+        let op: AdminOpOf<Test> = AdminOp::SetConfirmer(Some(other.clone()));
+        assert!(call_extension(&contract, 0x5873, (COLLECTION, op)).decode::<bool>());
+        let call = call_extension(&contract, 0x267a, (COLLECTION, DeferredOpOf::<Test>::Destroy));
+        assert!(call.result.is_err());
+        ContractsUniques::on_idle(1, Weight::from_ref_time(u64::MAX));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+
+        crate::AdminConfirmers::<Test>::remove(COLLECTION);
+        let call = call_extension(&contract, 0x267a, (COLLECTION, DeferredOpOf::<Test>::Destroy));
+        assert!(call.result.is_ok());
+        ContractsUniques::on_idle(2, Weight::from_ref_time(u64::MAX));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), None);
+    });
+}
+
+#[test]
+fn deferred_ops_are_cancelled_by_their_submitter() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+        let mint = DeferredOp::MintBatch { owner: BOB, count: 1 };
+        for _ in 0..4 {
+            assert!(call_extension(&contract, 0x267a, (COLLECTION, &mint)).result.is_ok());
+        }
+        let call = call_extension(&contract, 0x267a, (COLLECTION, &mint));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call submit_deferred_op"))
+        );
+
+        let call = call_extension(&other, 0x9c83, 2u32);
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call cancel_deferred_op"))
+        );
+        assert!(call_extension(&contract, 0x9c83, 2u32).result.is_ok());
+        assert_eq!(crate::DeferredQueue::<Test>::get().into_inner(), vec![0, 1, 3]);
+        assert!(call_extension(&contract, 0x9c83, 2u32).result.is_err());
+        assert!(call_extension(&contract, 0x267a, (COLLECTION, &mint)).result.is_ok());
+    });
+}
+
+#[test]
+fn calls_racing_with_destroy_report_collection_destroyed() {
+    use crate::status::*;
+
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let destroy = DeferredOpOf::<Test>::Destroy;
+        let assert_destroyed = |contract: &AccountId| {
+            let artwork = BlakeTwo256::hash(b"artwork");
+            let calls = [
+                call_extension(contract, 0xdb20, (COLLECTION, ITEM, BOB)),
+                call_extension(contract, 0x3df4, (COLLECTION, vec![(ITEM, BOB)], false)),
+                call_extension(contract, 0x1932, (COLLECTION, ITEM, BOB)),
+                call_extension(contract, 0x6b71, (COLLECTION, ITEM, contract)),
+                call_extension(contract, 0xaeed, (COLLECTION, BOB)),
+                call_extension(contract, 0x615c, (COLLECTION, BOB, artwork)),
+            ];
+            for call in calls {
+                assert_eq!(call.result, Ok((COLLECTION_DESTROYED, vec![])));
+            }
+        };
+        assert_eq!(call_extension(&contract, 0x267a, (COLLECTION, &destroy)).decode::<u32>(), 0);
+
+        // The items of a collection queued for destruction stay as they are.
+        assert_destroyed(&contract);
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(contract.clone()));
+        let mint = DeferredOp::MintBatch { owner: BOB, count: 1 };
+        assert!(call_extension(&contract, 0x267a, (COLLECTION, &mint)).result.is_err());
+
+        assert!(call_extension(&contract, 0x9c83, 0u32).result.is_ok());
+        let call = call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((SUCCESS, vec![])));
+
+        assert_eq!(call_extension(&contract, 0x267a, (COLLECTION, &destroy)).decode::<u32>(), 1);
+        ContractsUniques::on_idle(1, Weight::from_ref_time(u64::MAX));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), None);
+        assert_destroyed(&contract);
+
+        // A collection created again under the id of a destroyed one can be used again.
+        assert_ok!(Uniques::force_create(
+            RuntimeOrigin::root(),
+            COLLECTION,
+            contract.clone(),
+            false
+        ));
+        assert_eq!(call_extension(&contract, 0xaeed, (COLLECTION, BOB)).decode::<u32>(), 0);
+    });
+}
+
+#[test]
+fn deferred_mints_to_a_collection_queued_for_destruction_are_dropped() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let mint = DeferredOp::MintBatch { owner: BOB, count: 3 };
+        assert!(call_extension(&contract, 0x267a, (COLLECTION, &mint)).result.is_ok());
+        ContractsUniques::on_idle(1, deferred_mint_steps(1));
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(BOB));
+
+        let destroy = DeferredOpOf::<Test>::Destroy;
+        assert!(call_extension(&contract, 0x267a, (COLLECTION, destroy)).result.is_ok());
+        ContractsUniques::on_idle(2, Weight::from_ref_time(u64::MAX));
+        assert_eq!(Uniques::owner(COLLECTION, 1), None);
+        assert_eq!(crate::Destructions::<Test>::get(COLLECTION), Some(crate::Destruction::Done));
+        assert!(crate::DeferredQueue::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn watcher_is_notified_of_transfers_outside_the_extension() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let watcher = deploy_watcher();
+        let caller = ContractsUniques::watcher_caller(&COLLECTION);
+        Balances::make_free_balance_be(&caller, 1_000_000);
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, Some(&watcher))).result.is_ok());
+        let call = call_extension(&contract, 0x47e5, COLLECTION);
+        assert_eq!(call.decode::<Option<AccountId>>(), Some(watcher.clone()));
+
+        // The walk tells transfers apart once it saw the owner before.
+        ContractsUniques::on_idle(1, Weight::from_ref_time(u64::MAX));
+        let seen = crate::WatchedOwners::<Test>::get(COLLECTION, ITEM);
+        assert_eq!(seen, Some((1, contract.clone())));
+
+        // The watcher is not told about the transfers of the extension.
+        assert!(call_extension(&contract, 0xdb20, (COLLECTION, ITEM, CHARLIE)).result.is_ok());
+        ContractsUniques::on_idle(2, Weight::from_ref_time(u64::MAX));
+        assert_eq!(last_watcher_input(&watcher), None);
+
+        assert_ok!(Uniques::transfer(RuntimeOrigin::signed(CHARLIE), COLLECTION, ITEM, BOB));
+        ContractsUniques::on_idle(3, Weight::from_ref_time(u64::MAX));
+        let mut expected = crate::ON_TRANSFER_SELECTOR.to_vec();
+        (COLLECTION, ITEM, CHARLIE, BOB).encode_to(&mut expected);
+        assert_eq!(last_watcher_input(&watcher), Some(expected));
+        assert_eq!(last_watcher_caller(&watcher), Some(caller));
+        assert!(crate::WatchCursor::<Test>::get().is_none());
+    });
+}
+
+#[test]
+fn owners_seen_for_a_watcher_are_removed_once_it_is_replaced() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 3);
+        let watcher = deploy_watcher();
+        Balances::make_free_balance_be(&ContractsUniques::watcher_caller(&COLLECTION), 1_000_000);
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, Some(&watcher))).result.is_ok());
+        ContractsUniques::on_idle(1, Weight::from_ref_time(u64::MAX));
+        assert_eq!(crate::WatchedOwners::<Test>::iter_prefix(COLLECTION).count(), 4);
+
+        // Replacing the watcher pauses the walk of the collection until the owners are gone.
+        let other = deploy_fixture_with_salt(vec![1]);
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, Some(&other))).result.is_ok());
+        assert!(crate::StaleWatchedOwners::<Test>::contains_key(COLLECTION));
+        let limit = <Test as frame_system::Config>::DbWeight::get().reads_writes(3, 3);
+        ContractsUniques::clear_stale_watched_owners(limit);
+        assert_eq!(crate::WatchedOwners::<Test>::iter_prefix(COLLECTION).count(), 2);
+        ContractsUniques::notify_watchers(Weight::from_ref_time(u64::MAX));
+        assert_eq!(crate::WatchedOwners::<Test>::iter_prefix(COLLECTION).count(), 2);
+        ContractsUniques::on_idle(2, Weight::from_ref_time(u64::MAX));
+        assert!(!crate::StaleWatchedOwners::<Test>::contains_key(COLLECTION));
+        assert_eq!(crate::WatchedOwners::<Test>::iter_prefix(COLLECTION).count(), 0);
+
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, None::<AccountId>)).result.is_ok());
+        assert!(crate::StaleWatchedOwners::<Test>::contains_key(COLLECTION));
+        ContractsUniques::on_idle(3, Weight::from_ref_time(u64::MAX));
+        assert!(!crate::StaleWatchedOwners::<Test>::contains_key(COLLECTION));
+    });
+}
+
+#[test]
+fn watcher_is_a_contract_set_by_the_collection_owner() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+
+        let call = call_extension(&other, 0x5723, (COLLECTION, Some(&other)));
+        assert_eq!(
+            call.result,
+            Err(DispatchError::Other("ChainExtension failed to call set_watcher"))
+        );
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, Some(BOB))).result.is_err());
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, Some(&other))).result.is_ok());
+
+        assert!(call_extension(&contract, 0x5723, (COLLECTION, None::<AccountId>)).result.is_ok());
+        let call = call_extension(&contract, 0x47e5, COLLECTION);
+        assert_eq!(call.decode::<Option<AccountId>>(), None);
+    });
+}
+
+#[test]
+fn sequences_encoded_in_place_match_collected_ones() {
+    // Lengths on both sides of the boundaries between compact encoding modes.
+    for n in [0u32, 1, 63, 64, 16_383, 16_384] {
+        let output = crate::output::Output::encode_seq(0..n);
+        assert_eq!(output.as_bytes(), &(0..n).collect::<Vec<_>>().encode()[..]);
+    }
+}
+
+/// Mints items `0..n` of `COLLECTION` to `contract` next to `ITEM`.
+fn mint_items(contract: &AccountId, n: u32) {
+    for item in 0..n {
+        let origin = RuntimeOrigin::signed(contract.clone());
+        assert_ok!(Uniques::mint(origin, COLLECTION, item, contract.clone()));
+    }
+}
+
+#[test]
+fn transfer_batch_moves_all_items_or_none() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 3);
+
+        let batch = vec![(0u32, BOB), (1, CHARLIE), (2, contract.clone())];
+        let call = call_extension(&contract, 0x3df4, (COLLECTION, batch, false));
+        assert_eq!(call.result, Ok((crate::status::TRANSFER_TO_SELF, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(contract.clone()));
+
+        let batch = vec![(0u32, BOB), (1, CHARLIE), (2, BOB)];
+        let call = call_extension(&contract, 0x3df4, (COLLECTION, batch, false));
+        assert_eq!(call.decode::<(u32, u32)>(), (3, crate::status::SUCCESS));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(CHARLIE));
+        assert_eq!(Uniques::owner(COLLECTION, 2), Some(BOB));
+    });
+}
+
+#[test]
+fn best_effort_batch_stops_when_out_of_gas() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 4);
+
+        let input = (COLLECTION, vec![(0u32, BOB)], true).encode();
+        let single = call_extension_raw(&contract, 0x3df4, &input);
+        assert_eq!(single.decode::<(u32, u32)>(), (1, crate::status::SUCCESS));
+
+        // Enough gas for two transfers, and half of the weight of another one to spare.
+        let backend = <crate::backend::Uniques<Test> as NftBackend<AccountId>>::transfer_weight();
+        let per_transfer = backend
+            .saturating_add(<() as crate::WeightInfo>::transfer());
+        let needed = per_transfer
+            .saturating_add(<() as crate::WeightInfo>::forget_item(4))
+            .saturating_add(<() as crate::WeightInfo>::bump_nonce());
+        let gas_limit = single
+            .gas_consumed
+            .saturating_add(needed)
+            .saturating_add(per_transfer.saturating_div(2));
+        let input = (COLLECTION, vec![(1u32, BOB), (2, BOB), (3, BOB)], true).encode();
+        let call = call_extension_with_gas_limit(&contract, 0x3df4, &input, gas_limit);
+        assert_eq!(call.decode::<(u32, u32)>(), (2, crate::status::SUCCESS));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 2), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 3), Some(contract.clone()));
+    });
+}
+
+#[test]
+fn best_effort_batch_stops_at_rejected_transfer() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 3);
+
+        let batch = vec![(0u32, BOB), (1, contract.clone()), (2, BOB)];
+        let call = call_extension(&contract, 0x3df4, (COLLECTION, batch, true));
+        assert_eq!(call.decode::<(u32, u32)>(), (1, crate::status::TRANSFER_TO_SELF));
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(contract.clone()));
+        assert_eq!(Uniques::owner(COLLECTION, 2), Some(contract.clone()));
+    });
+}
+
+#[test]
+fn best_effort_batch_stops_at_transfer_the_pallet_refuses() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        mint_items(&contract, 3);
+        assert_ok!(Uniques::freeze(RuntimeOrigin::signed(contract.clone()), COLLECTION, 1));
+
+        let batch = vec![(0u32, BOB), (1, BOB), (2, BOB)];
+        let call = call_extension(&contract, 0x3df4, (COLLECTION, batch.clone(), false));
+        assert!(call.result.is_err());
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(contract.clone()));
+
+        let call = call_extension(&contract, 0x3df4, (COLLECTION, batch, true));
+        assert_eq!(call.decode::<(u32, u32)>(), (1, crate::status::TRANSFER_FAILED));
+        assert_eq!(Uniques::owner(COLLECTION, 0), Some(BOB));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(contract.clone()));
+        assert_eq!(Uniques::owner(COLLECTION, 2), Some(contract.clone()));
+    });
+}
+
+#[test]
+fn malformed_input_never_panics() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        // xorshift64, so the inputs are arbitrary but the same on every run.
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next_byte = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        };
+
+        for func_id in FUNC_IDS {
+            let mut inputs = vec![vec![], vec![0x00; 200], vec![0xff; 200]];
+            for len in [1, 4, 8, 33, 36, 68, 100, 1000] {
+                inputs.push((0..len).map(|_| next_byte()).collect());
+            }
+            for input in inputs {
+                // Rejecting the input is fine, as long as the runtime does not panic and
+                // only returns registered status codes.
+                if let Ok((status, _)) = call_extension_raw(&contract, func_id, &input).result {
+                    assert!(crate::status::is_registered(status));
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn unknown_func_id_fails() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+
+        let call = call_extension(&contract, 0xffff, ());
+        assert_eq!(call.result, Err(DispatchError::Other("Unimplemented func_id")));
+    });
+}
+
+#[test]
+fn custom_func_ids_are_served_by_the_handler() {
+    use crate::custom::CUSTOM_FUNC_IDS;
+
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let grantee = deploy_fixture_with_salt(vec![1]);
+        assert!(FUNC_IDS.iter().all(|func_id| !CUSTOM_FUNC_IDS.contains(func_id)));
+
+        let call = call_extension(&contract, 0xf001, COLLECTION);
+        assert_eq!(call.decode::<AccountId>(), contract.clone());
+        let call = call_extension(&contract, 0xf002, COLLECTION);
+        assert_eq!(call.result, Err(DispatchError::Other("Unimplemented func_id")));
+
+        // Capabilities cover custom function IDs like built-in ones.
+        let call = call_extension(&contract, 0x253d, (&grantee, COLLECTION, vec![0xf001u16]));
+        assert!(call.result.is_ok());
+        assert!(call_extension(&grantee, 0x0d2d, Some(&contract)).result.is_ok());
+        let call = call_extension(&grantee, 0xf001, COLLECTION);
+        assert_eq!(call.decode::<AccountId>(), contract.clone());
+        assert!(call_extension(&grantee, 0xf001, COLLECTION + 1).result.is_err());
+    });
+}
+
+#[test]
+fn burn_checks_the_expected_owner() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+        // The item changed hands after the contract looked up its owner.
+        assert_ok!(Uniques::transfer(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM,
+            BOB
+        ));
+        assert!(call_extension(&contract, 0xce8e, (COLLECTION, ITEM, Some(7u32))).result.is_ok());
+
+        let call = call_extension(&contract, 0x6b71, (COLLECTION, ITEM, &contract));
+        assert_eq!(call.result, Ok((crate::status::OWNER_MISMATCH, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), Some(BOB));
+
+        // Only the owner of an item or the admin of its collection can burn it.
+        let call = call_extension(&other, 0x6b71, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Err(DispatchError::Other("ChainExtension failed to call burn")));
+
+        let call = call_extension(&contract, 0x6b71, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((crate::status::SUCCESS, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, ITEM), None);
+        assert!(!crate::FreezeReasons::<Test>::contains_key(COLLECTION, ITEM));
+        let call = call_extension(&contract, 0x6b71, (COLLECTION, ITEM, BOB));
+        assert_eq!(call.result, Ok((crate::status::OWNER_MISMATCH, vec![])));
+    });
+}
+
+#[test]
+fn allowance_combines_item_and_collection_approvals() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let allowance = |operator: &AccountId, item: Option<u32>| {
+            call_extension(&contract, 0x4790, (COLLECTION, &contract, operator, item))
+                .decode::<bool>()
+        };
+
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(allowance(&BOB, Some(ITEM)));
+        assert!(!allowance(&BOB, Some(ITEM + 1)));
+        assert!(!allowance(&BOB, None));
+        assert!(!allowance(&CHARLIE, Some(ITEM)));
+
+        // The capability to transfer approves every item of the collection.
+        let call = call_extension(&contract, 0x253d, (CHARLIE, COLLECTION, vec![0xdb20u16]));
+        assert!(call.result.is_ok());
+        assert!(allowance(&CHARLIE, Some(ITEM)));
+        assert!(allowance(&CHARLIE, None));
+        let other_collection = (COLLECTION + 1, &contract, CHARLIE, None::<u32>);
+        assert!(!call_extension(&contract, 0x4790, other_collection).decode::<bool>());
+
+        // What `allowance` reports is what `transfer` enforces.
+        let other = deploy_fixture_with_salt(vec![1]);
+        let origin = RuntimeOrigin::signed(contract.clone());
+        assert_ok!(Uniques::mint(origin, COLLECTION, 1, contract.clone()));
+        assert!(!allowance(&other, Some(1)));
+        assert!(call_extension(&other, 0xdb20, (COLLECTION, 1u32, BOB)).result.is_err());
+        let call = call_extension(&contract, 0x253d, (&other, COLLECTION, vec![0xdb20u16]));
+        assert!(call.result.is_ok());
+        assert!(allowance(&other, Some(1)));
+        assert!(call_extension(&other, 0x0d2d, Some(&contract)).result.is_ok());
+        let call = call_extension(&other, 0xdb20, (COLLECTION, 1u32, BOB));
+        assert_eq!(call.result, Ok((crate::status::SUCCESS, vec![])));
+        assert_eq!(Uniques::owner(COLLECTION, 1), Some(BOB));
+
+        // Approvals of an item lapse once it changes hands.
+        assert_ok!(Uniques::transfer(
+            RuntimeOrigin::signed(contract.clone()),
+            COLLECTION,
+            ITEM,
+            BOB
+        ));
+        assert!(!allowance(&BOB, Some(ITEM)));
+    });
+}
+
+#[test]
+fn provenance_is_anchored_at_mint_time() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let artwork = H256::repeat_byte(7);
+        let verify = |item: u32, hash: H256| {
+            call_extension(&contract, 0x27cd, (COLLECTION, item, hash)).decode::<bool>()
+        };
+
+        let item = call_extension(&contract, 0x615c, (COLLECTION, BOB, artwork)).decode::<u32>();
+        assert_eq!(Uniques::owner(COLLECTION, item), Some(BOB));
+        assert!(verify(item, artwork));
+        assert!(!verify(item, H256::zero()));
+        let plain = call_extension(&contract, 0xaeed, (COLLECTION, BOB)).decode::<u32>();
+        assert!(!verify(plain, H256::zero()));
+
+        // An item minted again with the id of a burned one does not inherit its hash.
+        let call = call_extension(&contract, 0x6b71, (COLLECTION, item, BOB));
+        assert_eq!(call.result, Ok((crate::status::SUCCESS, vec![])));
+        assert!(!verify(item, artwork));
+        assert_ok!(Uniques::mint(RuntimeOrigin::signed(contract.clone()), COLLECTION, item, BOB));
+        assert!(!verify(item, artwork));
+    });
+}
+
+#[test]
+fn flat_outputs_keep_fields_at_fixed_offsets() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let owner = |item: u32, format: u8| {
+            call_extension(&contract, 0x162d, (COLLECTION, item, format)).result
+        };
+
+        let mut present = vec![1];
+        present.extend(contract.encode());
+        assert_eq!(owner(ITEM, 1), Ok((0, present)));
+        let mut absent = vec![0];
+        absent.extend([0; 32]);
+        assert_eq!(owner(ITEM + 1, 1), Ok((0, absent)));
+        // Without the flag, or with the byte of the default format, the output is SCALE.
+        assert_eq!(owner(ITEM + 1, 0), Ok((0, None::<AccountId>.encode())));
+        let call = call_extension(&contract, 0x162d, (COLLECTION, ITEM + 1));
+        assert_eq!(call.result, Ok((0, None::<AccountId>.encode())));
+        assert_eq!(
+            owner(ITEM, 2),
+            Err(DispatchError::Other("ChainExtension got an unknown output format"))
+        );
+        // Nothing but the format byte may follow the input of the query.
+        let call = call_extension(&contract, 0x162d, (COLLECTION, ITEM, 1u8, 1u8));
+        assert_eq!(call.result, Err(pallet_contracts::Error::<Test>::DecodingFailed.into()));
+        let call = call_extension(&contract, 0x162d, (COLLECTION, ITEM, [0u8; 64]));
+        assert_eq!(call.result, Err(pallet_contracts::Error::<Test>::DecodingFailed.into()));
+
+        // Optional fields within the output get a byte of their own.
+        assert!(call_extension(&contract, 0xce8e, (COLLECTION, ITEM, Some(7u32))).result.is_ok());
+        let call = call_extension(&contract, 0x4cb3, (COLLECTION, ITEM, 1u8));
+        assert_eq!(call.result, Ok((0, vec![1, 1, 1, 7, 0, 0, 0])));
+        assert!(call_extension(&contract, 0xa49a, (COLLECTION, ITEM)).result.is_ok());
+        let call = call_extension(&contract, 0x4cb3, (COLLECTION, ITEM, 1u8));
+        assert_eq!(call.result, Ok((0, vec![1, 0, 0, 0, 0, 0, 0])));
+    });
+}
+
+#[test]
+fn nonces_count_the_mutations_of_each_contract() {
+    new_test_ext().execute_with(|| {
+        let contract = setup();
+        let other = deploy_fixture_with_salt(vec![1]);
+        let nonce = |contract: &AccountId| {
+            call_extension(contract, 0xdfc4, (COLLECTION,)).decode::<u64>()
+        };
+        assert_eq!(nonce(&contract), 0);
+
+        let item = call_extension(&contract, 0xaeed, (COLLECTION, &contract)).decode::<u32>();
+        assert_eq!(nonce(&contract), 1);
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, item, BOB)).result.is_ok());
+        assert_eq!(nonce(&contract), 2);
+        // Revoking approvals of items of a collection counts once per collection.
+        assert!(call_extension(&contract, 0x1932, (COLLECTION, ITEM, BOB)).result.is_ok());
+        assert!(call_extension(&contract, 0x2612, (BOB,)).result.is_ok());
+        assert_eq!(nonce(&contract), 4);
+
+        // Calls that change nothing leave the nonce as it is.
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &contract));
+        assert_eq!(call.result, Ok((crate::status::TRANSFER_TO_OWNER, vec![])));
+        assert!(call_extension(&contract, 0xce8e, (COLLECTION, 7u32, None::<u32>)).result.is_err());
+        assert_eq!(nonce(&contract), 4);
+
+        // Each contract counts its own calls.
+        let call = call_extension(&contract, 0xdb20, (COLLECTION, ITEM, &other));
+        assert_eq!(call.result, Ok((crate::status::SUCCESS, vec![])));
+        assert_eq!(nonce(&contract), 5);
+        assert_eq!(nonce(&other), 0);
+        assert!(call_extension(&other, 0xce8e, (COLLECTION, ITEM, None::<u32>)).result.is_err());
+        assert_eq!(nonce(&other), 0);
+    });
+}
@@ -0,0 +1,133 @@
+//! Types shared between the pallet and the chain extension.
+
+use codec::{
+    Decode,
+    Encode,
+    MaxEncodedLen,
+};
+use scale_info::TypeInfo;
+
+use super::*;
+
+/// A statement that `owner` owned `item` of `collection` at `block_number`.
+///
+/// Attestations are committed to by their hash in [`pallet::Attestations`], so anyone
+/// holding one can have the runtime confirm it without trusting the presenter.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct OwnershipAttestation<BlockNumber, CollectionId, ItemId, AccountId> {
+    pub block_number: BlockNumber,
+    pub collection: CollectionId,
+    pub item: ItemId,
+    pub owner: AccountId,
+}
+
+/// [`OwnershipAttestation`] for the types of the runtime.
+pub type OwnershipAttestationOf<T> = OwnershipAttestation<
+    <T as frame_system::Config>::BlockNumber,
+    CollectionIdOf<T>,
+    ItemIdOf<T>,
+    <T as frame_system::Config>::AccountId,
+>;
+
+/// A destructive operation on a collection that its confirmer has to approve.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum AdminOp<ItemId, AccountId> {
+    /// Destroys the collection along with all of its items.
+    Destroy,
+    /// Transfers `item` to `dest` regardless of its owner.
+    ForceTransfer { item: ItemId, dest: AccountId },
+    /// Replaces the account that confirms admin operations, or removes it.
+    SetConfirmer(Option<AccountId>),
+}
+
+/// [`AdminOp`] for the types of the runtime.
+pub type AdminOpOf<T> = AdminOp<ItemIdOf<T>, <T as frame_system::Config>::AccountId>;
+
+/// An admin operation waiting for its confirmation.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct PendingAdminOp<Op, AccountId, BlockNumber> {
+    /// The operation to execute once confirmed.
+    pub op: Op,
+    /// The collection owner who proposed the operation.
+    pub proposer: AccountId,
+    /// The last block the operation can be confirmed at.
+    pub expires_at: BlockNumber,
+}
+
+/// [`PendingAdminOp`] for the types of the runtime.
+pub type PendingAdminOpOf<T> = PendingAdminOp<
+    AdminOpOf<T>,
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+/// A heavy operation on a collection, executed step by step in `on_idle`.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum DeferredOp<AccountId> {
+    /// Mints `count` items with unused ids to `owner`, one per step.
+    MintBatch { owner: AccountId, count: u32 },
+    /// Destroys the collection along with all of its items, in a single step.
+    Destroy,
+}
+
+/// [`DeferredOp`] for the types of the runtime.
+pub type DeferredOpOf<T> = DeferredOp<<T as frame_system::Config>::AccountId>;
+
+/// A deferred operation waiting in the queue.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct QueuedOp<Op, CollectionId, AccountId> {
+    /// The operation, reduced to the steps that are left.
+    pub op: Op,
+    pub collection: CollectionId,
+    /// The account the operation is executed on behalf of.
+    pub submitter: AccountId,
+}
+
+/// [`QueuedOp`] for the types of the runtime.
+pub type QueuedOpOf<T> =
+    QueuedOp<DeferredOpOf<T>, CollectionIdOf<T>, <T as frame_system::Config>::AccountId>;
+
+/// How far the destruction of a collection through the extension got.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum Destruction {
+    /// A deferred operation destroying the collection waits in the queue.
+    Queued,
+    /// The collection was destroyed.
+    Done,
+}
+
+/// A failed call of a contract to the extension, as recorded by builds with the `debug`
+/// feature.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct LastError {
+    /// The function ID the contract called.
+    pub func_id: u16,
+    /// The error the call failed with, before it was converted for the contract.
+    pub error: sp_runtime::DispatchError,
+}
+
+/// An item id with a successor, so unused ids can be searched for in order.
+pub trait Incrementable: Sized {
+    /// The first id of a collection.
+    fn initial_value() -> Self;
+    /// Returns the id following `self`, or `None` if `self` is the last one.
+    fn increment(&self) -> Option<Self>;
+}
+
+macro_rules! impl_incrementable {
+    ($($t:ty),+) => {
+        $(
+            impl Incrementable for $t {
+                fn initial_value() -> Self {
+                    0
+                }
+
+                fn increment(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )+
+    };
+}
+
+impl_incrementable!(u8, u16, u32, u64, u128);
@@ -0,0 +1,253 @@
+//! Notifications of transfers made outside the extension.
+//!
+//! Contracts mirroring the items of a collection, e.g. an index or the state of a game,
+//! only learn about the transfers they make themselves. The owner of a collection can
+//! register a watcher contract, which is called with [`ON_TRANSFER_SELECTOR`] and the
+//! SCALE-encoded `(collection, item, from, to)` of every transfer made through the NFT
+//! pallet's extrinsics.
+//!
+//! The NFT pallets offer no hook on transfers, so in `on_idle`, with whatever weight the
+//! block leaves unused, the pallet walks the items of the watched collections and
+//! records the owner of each. The watcher is notified whenever an item has another owner
+//! than on the previous visit of the walk, which starts over once it went through every
+//! watched collection. Transfers are therefore reported once the walk visited an item
+//! since the watcher was registered, and an item transferred several times between two
+//! visits is reported once, from the owner of the earlier visit to the current one. The
+//! extension records the owner of the items it transfers, so these transfers are left
+//! out unless the item also changed hands outside the extension since the last visit.
+//!
+//! Watchers are called from an account derived from [`WATCHER_PALLET_ID`] and the
+//! collection, see [`Pallet::watcher_caller`], which nobody holds the keys of. A watcher
+//! checks that its caller is this account to tell notifications from calls anybody could
+//! make with forged transfers. The account pays for the storage the watcher uses, up to
+//! `WatcherDepositLimit` per notification, so whoever registers a watcher that stores
+//! anything funds it first; funds sent to it cannot be taken out again.
+//!
+//! Notifications are best-effort: they are dropped if the watcher runs out of its
+//! `WatcherGasLimit` or fails, and the watcher's changes are reverted then.
+//!
+//! The owners seen for a watcher are removed in `on_idle` once it is unregistered or
+//! replaced, or its collection is destroyed. A new watcher is notified once they are gone.
+
+use codec::Encode;
+use frame_support::PalletId;
+use sp_runtime::traits::{
+    AccountIdConversion,
+    Zero,
+};
+
+use super::*;
+
+/// The selector of the message watchers are called with, the same as of the `on_transfer`
+/// message of an ink! trait named `Psp02Watcher`.
+pub const ON_TRANSFER_SELECTOR: [u8; 4] = [0x44, 0x5d, 0xcf, 0x12];
+
+/// The id the accounts watchers are called from are derived from.
+pub const WATCHER_PALLET_ID: PalletId = PalletId(*b"psp02/wt");
+
+/// A transfer made outside the extension, as `(collection, item, from, to)`.
+type ExternalTransfer<T> = (
+    CollectionIdOf<T>,
+    ItemIdOf<T>,
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::AccountId,
+);
+
+impl<T: Config> Pallet<T> {
+    /// Registers `watcher` to be notified of transfers of `collection` made outside the
+    /// extension, or stops notifying if `watcher` is `None`.
+    ///
+    /// Only the collection owner can set the watcher, which has to be a contract.
+    pub(crate) fn set_watcher(
+        who: &T::AccountId,
+        collection: &CollectionIdOf<T>,
+        watcher: Option<T::AccountId>,
+    ) -> DispatchResult {
+        ensure!(
+            T::Backend::collection_owner(collection).as_ref() == Some(who),
+            Error::<T>::NotCollectionOwner
+        );
+        // The owners seen for the current watcher, if any, are of no use to another one.
+        if Watchers::<T>::contains_key(collection) {
+            StaleWatchedOwners::<T>::insert(collection, ());
+        }
+        match watcher {
+            Some(watcher) => {
+                ensure!(
+                    pallet_contracts::Pallet::<T>::code_hash(&watcher).is_some(),
+                    Error::<T>::NotContract
+                );
+                Watchers::<T>::insert(collection, (who, watcher));
+                // Owners seen for an earlier watcher may be outdated.
+                WatcherRegistrations::<T>::mutate(collection, |count| {
+                    *count = count.wrapping_add(1)
+                });
+            },
+            None => Watchers::<T>::remove(collection),
+        }
+        Ok(())
+    }
+
+    /// Unregisters the watcher of the destroyed `collection`, if any.
+    pub(crate) fn forget_watcher(collection: &CollectionIdOf<T>) {
+        if Watchers::<T>::take(collection).is_some() {
+            StaleWatchedOwners::<T>::insert(collection, ());
+        }
+    }
+
+    /// Returns the account the watcher of `collection` is called from.
+    pub fn watcher_caller(collection: &CollectionIdOf<T>) -> T::AccountId {
+        WATCHER_PALLET_ID.into_sub_account_truncating(collection)
+    }
+
+    /// Records that the extension transferred `item` from `from` to `to`, so its watcher is
+    /// not notified. Transfers made outside the extension before are still reported.
+    pub(crate) fn note_extension_transfer(
+        collection: &CollectionIdOf<T>,
+        item: &ItemIdOf<T>,
+        from: &T::AccountId,
+        to: &T::AccountId,
+    ) {
+        let registration = WatcherRegistrations::<T>::get(collection);
+        WatchedOwners::<T>::mutate_exists(collection, item, |seen| {
+            if seen.as_ref() == Some(&(registration, from.clone())) {
+                *seen = Some((registration, to.clone()));
+            }
+        });
+    }
+
+    /// Walks the items of watched collections and notifies the watchers of the transfers
+    /// made outside the extension for as long as the work fits into `limit`, and returns
+    /// the weight used.
+    pub(crate) fn notify_watchers(limit: Weight) -> Weight {
+        let mut used = T::DbWeight::get().reads(1);
+        if used.ref_time() > limit.ref_time() {
+            return Weight::zero()
+        }
+        let (mut collection, mut start_after) = match WatchCursor::<T>::get() {
+            Some(cursor) => cursor,
+            None => match Watchers::<T>::iter_keys().next() {
+                Some(collection) => (collection, None),
+                None => return used,
+            },
+        };
+
+        // Any item may turn out to need a notification, so every step leaves room for one.
+        let step = T::WeightInfo::scan_watched_item()
+            .saturating_add(T::WatcherGasLimit::get())
+            .saturating_add(T::WeightInfo::notify_watcher());
+        loop {
+            if used.saturating_add(step).ref_time() > limit.ref_time() {
+                WatchCursor::<T>::put((collection, start_after));
+                return used
+            }
+            used = used.saturating_add(T::WeightInfo::scan_watched_item());
+            // Owners seen for an earlier watcher are still being removed.
+            let next = Watchers::<T>::get(collection)
+                .filter(|_| !StaleWatchedOwners::<T>::contains_key(collection))
+                .and_then(|(_, watcher)| {
+                    let mut items = T::Backend::items_of(&collection, start_after.as_ref(), 1);
+                    items.pop().map(|(item, owner)| (watcher, item, owner))
+                });
+            let (watcher, item, owner) = match next {
+                Some(next) => next,
+                None => {
+                    // The collection was walked to its end or is no longer watched. The
+                    // walk goes on with the next watched collection, or starts over with
+                    // the first one in the next block. Collections destroyed outside the
+                    // extension lose their watcher here.
+                    used = used.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+                    if T::Backend::collection_owner(&collection).is_none() {
+                        Self::forget_watcher(&collection);
+                    }
+                    let key = Watchers::<T>::hashed_key_for(collection);
+                    match Watchers::<T>::iter_keys_from(key).next() {
+                        Some(next) => {
+                            collection = next;
+                            start_after = None;
+                            continue
+                        },
+                        None => {
+                            WatchCursor::<T>::kill();
+                            return used
+                        },
+                    }
+                },
+            };
+            start_after = Some(item);
+            if let Some(from) = Self::note_watched_owner(&collection, &item, &owner) {
+                let transfer = (collection, item, from, owner);
+                used = used.saturating_add(Self::notify_watcher(watcher, transfer));
+            }
+        }
+    }
+
+    /// Records `owner` as the owner of `item` the walk saw, and returns the owner it saw on
+    /// its previous visit if that was another one.
+    pub(crate) fn note_watched_owner(
+        collection: &CollectionIdOf<T>,
+        item: &ItemIdOf<T>,
+        owner: &T::AccountId,
+    ) -> Option<T::AccountId> {
+        let registration = WatcherRegistrations::<T>::get(collection);
+        let seen = WatchedOwners::<T>::get(collection, item)
+            .filter(|(seen_for, _)| *seen_for == registration)
+            .map(|(_, seen)| seen);
+        if seen.as_ref() == Some(owner) {
+            return None
+        }
+        WatchedOwners::<T>::insert(collection, item, (registration, owner));
+        seen
+    }
+
+    /// Calls `watcher` with `transfer` from the watcher caller of the collection, and
+    /// returns the weight used.
+    fn notify_watcher(watcher: T::AccountId, transfer: ExternalTransfer<T>) -> Weight {
+        let caller = Self::watcher_caller(&transfer.0);
+        let mut data = ON_TRANSFER_SELECTOR.to_vec();
+        transfer.encode_to(&mut data);
+        let result = pallet_contracts::Pallet::<T>::bare_call(
+            caller,
+            watcher,
+            Zero::zero(),
+            T::WatcherGasLimit::get(),
+            Some(T::WatcherDepositLimit::get()),
+            data,
+            false,
+        );
+        result.gas_consumed.saturating_add(T::WeightInfo::notify_watcher())
+    }
+
+    /// Removes the owners seen for unregistered or replaced watchers and for destroyed
+    /// collections for as long as the work fits into `limit`, and returns the weight used.
+    pub(crate) fn clear_stale_watched_owners(limit: Weight) -> Weight {
+        let used = T::DbWeight::get().reads(1);
+        let done = T::DbWeight::get().writes(1);
+        if used.saturating_add(done).ref_time() > limit.ref_time() {
+            return Weight::zero()
+        }
+        let collection = match StaleWatchedOwners::<T>::iter_keys().next() {
+            Some(collection) => collection,
+            None => return used,
+        };
+        let budget = limit.saturating_sub(used).saturating_sub(done);
+        let per_owner = T::DbWeight::get().reads_writes(1, 1);
+        let max = budget.ref_time() / per_owner.ref_time().max(1);
+        if max == 0 {
+            return used
+        }
+        let removed = WatchedOwners::<T>::clear_prefix(
+            collection,
+            max.try_into().unwrap_or(u32::MAX),
+            None,
+        );
+        let used = used.saturating_add(
+            T::DbWeight::get().reads_writes(removed.loops.into(), removed.unique.into()),
+        );
+        if removed.maybe_cursor.is_some() {
+            return used
+        }
+        StaleWatchedOwners::<T>::remove(collection);
+        used.saturating_add(done)
+    }
+}
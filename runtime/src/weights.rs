@@ -0,0 +1,398 @@
+//! Weights of the work the chain extension does on top of the NFT pallet.
+//!
+//! The weight of the NFT pallet call behind a function ID is taken from the backend and
+//! charged in addition to these.
+//!
+//! The values below are placeholders, not benchmark results. Every call is charged
+//! [`BASE`] plus its storage accesses, counted from the code, and every element it
+//! iterates over [`PER_ELEMENT`] plus the storage accesses of the element. Runtimes
+//! should replace them with weights generated from the benchmarks in `benchmarking.rs`:
+//! ```text
+//! ./target/release/node benchmark pallet \
+//!     --chain=dev --steps=50 --repeat=20 \
+//!     --pallet=pallet_contracts_uniques --extrinsic='*' \
+//!     --output=./runtime/src/weights/pallet_contracts_uniques.rs
+//! ```
+
+use frame_support::{
+    traits::Get,
+    weights::{
+        RuntimeDbWeight,
+        Weight,
+    },
+};
+#[cfg(test)]
+use frame_support::weights::constants::RocksDbWeight;
+use sp_std::marker::PhantomData;
+
+/// Placeholder ref time of a call, besides its storage accesses.
+pub const BASE: u64 = 20_000_000;
+/// Placeholder ref time per element a call iterates over, besides its storage accesses.
+pub const PER_ELEMENT: u64 = 5_000_000;
+/// Placeholder ref time per byte of input a call copies.
+pub const PER_BYTE: u64 = 1_000;
+
+fn base<W: Get<RuntimeDbWeight>>(reads: u64, writes: u64) -> Weight {
+    Weight::from_ref_time(BASE).saturating_add(W::get().reads_writes(reads, writes))
+}
+
+fn per_element<W: Get<RuntimeDbWeight>>(n: u32, reads: u64, writes: u64) -> Weight {
+    Weight::from_ref_time(PER_ELEMENT)
+        .saturating_add(W::get().reads_writes(reads, writes))
+        .saturating_mul(n as u64)
+}
+
+fn per_byte(l: u32) -> Weight {
+    Weight::from_ref_time(PER_BYTE).saturating_mul(l as u64)
+}
+
+/// Weight functions needed by the chain extension.
+pub trait WeightInfo {
+    fn owner() -> Weight;
+    fn transfer() -> Weight;
+    fn forget_item(d: u32) -> Weight;
+    fn approve(n: u32) -> Weight;
+    fn cancel_approval(n: u32) -> Weight;
+    fn approvals_to(n: u32) -> Weight;
+    fn revoke_all_approvals(n: u32) -> Weight;
+    fn attest_ownership() -> Weight;
+    fn verify_attestation() -> Weight;
+    fn set_metadata(l: u32) -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
+    fn freeze_status() -> Weight;
+    fn act_for() -> Weight;
+    fn acting_for() -> Weight;
+    fn admin_op() -> Weight;
+    fn confirm_admin_op() -> Weight;
+    fn cancel_admin_op() -> Weight;
+    fn pending_admin_op() -> Weight;
+    fn admin_confirmer() -> Weight;
+    fn next_free_item_id(n: u32) -> Weight;
+    fn mint_next(n: u32) -> Weight;
+    fn collections_owned(n: u32) -> Weight;
+    fn grant_capability(n: u32) -> Weight;
+    fn capabilities() -> Weight;
+    fn last_error() -> Weight;
+    fn publish_snapshot_root() -> Weight;
+    fn verify_snapshot_membership(n: u32) -> Weight;
+    fn set_memo() -> Weight;
+    fn memo() -> Weight;
+    fn set_transfer_cooldown() -> Weight;
+    fn transfer_cooldown() -> Weight;
+    fn free_holding() -> Weight;
+    fn submit_deferred_op() -> Weight;
+    fn cancel_deferred_op(n: u32) -> Weight;
+    fn deferred_op_status() -> Weight;
+    fn process_deferred_op() -> Weight;
+    fn set_watcher() -> Weight;
+    fn watcher() -> Weight;
+    fn scan_watched_item() -> Weight;
+    fn notify_watcher() -> Weight;
+    fn burn() -> Weight;
+    fn allowance() -> Weight;
+    fn anchor_provenance() -> Weight;
+    fn verify_provenance() -> Weight;
+    fn bump_nonce() -> Weight;
+    fn collection_nonce() -> Weight;
+    fn collection_destroyed() -> Weight;
+}
+
+/// Placeholder weights for the chain extension, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn owner() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn transfer() -> Weight {
+        base::<T::DbWeight>(6, 2)
+    }
+    fn forget_item(d: u32) -> Weight {
+        base::<T::DbWeight>(0, 0).saturating_add(per_element::<T::DbWeight>(d, 1, 1))
+    }
+    fn approve(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 1).saturating_add(per_element::<T::DbWeight>(n, 0, 0))
+    }
+    fn cancel_approval(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 1).saturating_add(per_element::<T::DbWeight>(n, 0, 0))
+    }
+    fn approvals_to(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 0).saturating_add(per_element::<T::DbWeight>(n, 1, 0))
+    }
+    fn revoke_all_approvals(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 1).saturating_add(per_element::<T::DbWeight>(n, 1, 0))
+    }
+    fn attest_ownership() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn verify_attestation() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn set_metadata(l: u32) -> Weight {
+        base::<T::DbWeight>(1, 0).saturating_add(per_byte(l))
+    }
+    fn freeze() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn thaw() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn freeze_status() -> Weight {
+        base::<T::DbWeight>(3, 0)
+    }
+    fn act_for() -> Weight {
+        base::<T::DbWeight>(0, 1)
+    }
+    fn acting_for() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn admin_op() -> Weight {
+        base::<T::DbWeight>(4, 1)
+    }
+    fn confirm_admin_op() -> Weight {
+        base::<T::DbWeight>(4, 2)
+    }
+    fn cancel_admin_op() -> Weight {
+        base::<T::DbWeight>(3, 1)
+    }
+    fn pending_admin_op() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn admin_confirmer() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn next_free_item_id(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 1).saturating_add(per_element::<T::DbWeight>(n, 1, 0))
+    }
+    fn mint_next(n: u32) -> Weight {
+        base::<T::DbWeight>(3, 2).saturating_add(per_element::<T::DbWeight>(n, 1, 0))
+    }
+    fn collections_owned(n: u32) -> Weight {
+        base::<T::DbWeight>(1, 0).saturating_add(per_element::<T::DbWeight>(n, 1, 0))
+    }
+    fn grant_capability(n: u32) -> Weight {
+        base::<T::DbWeight>(2, 1).saturating_add(per_element::<T::DbWeight>(n, 0, 0))
+    }
+    fn capabilities() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn last_error() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn publish_snapshot_root() -> Weight {
+        base::<T::DbWeight>(0, 1)
+    }
+    fn verify_snapshot_membership(n: u32) -> Weight {
+        base::<T::DbWeight>(1, 0).saturating_add(per_element::<T::DbWeight>(n, 0, 0))
+    }
+    fn set_memo() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn memo() -> Weight {
+        base::<T::DbWeight>(2, 0)
+    }
+    fn set_transfer_cooldown() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn transfer_cooldown() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn free_holding() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn submit_deferred_op() -> Weight {
+        base::<T::DbWeight>(5, 4)
+    }
+    fn cancel_deferred_op(n: u32) -> Weight {
+        base::<T::DbWeight>(3, 3).saturating_add(per_element::<T::DbWeight>(n, 0, 0))
+    }
+    fn deferred_op_status() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn process_deferred_op() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn set_watcher() -> Weight {
+        base::<T::DbWeight>(3, 3)
+    }
+    fn watcher() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn scan_watched_item() -> Weight {
+        base::<T::DbWeight>(5, 1)
+    }
+    fn notify_watcher() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn burn() -> Weight {
+        base::<T::DbWeight>(2, 5)
+    }
+    fn allowance() -> Weight {
+        base::<T::DbWeight>(3, 0)
+    }
+    fn anchor_provenance() -> Weight {
+        base::<T::DbWeight>(0, 1)
+    }
+    fn verify_provenance() -> Weight {
+        base::<T::DbWeight>(2, 0)
+    }
+    fn bump_nonce() -> Weight {
+        base::<T::DbWeight>(1, 1)
+    }
+    fn collection_nonce() -> Weight {
+        base::<T::DbWeight>(1, 0)
+    }
+    fn collection_destroyed() -> Weight {
+        base::<T::DbWeight>(2, 0)
+    }
+}
+
+// For the tests of this crate only. Runtimes use `SubstrateWeight` or weights generated
+// from the benchmarks.
+#[cfg(test)]
+impl WeightInfo for () {
+    fn owner() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn transfer() -> Weight {
+        base::<RocksDbWeight>(6, 2)
+    }
+    fn forget_item(d: u32) -> Weight {
+        base::<RocksDbWeight>(0, 0).saturating_add(per_element::<RocksDbWeight>(d, 1, 1))
+    }
+    fn approve(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 1).saturating_add(per_element::<RocksDbWeight>(n, 0, 0))
+    }
+    fn cancel_approval(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 1).saturating_add(per_element::<RocksDbWeight>(n, 0, 0))
+    }
+    fn approvals_to(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 0).saturating_add(per_element::<RocksDbWeight>(n, 1, 0))
+    }
+    fn revoke_all_approvals(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 1).saturating_add(per_element::<RocksDbWeight>(n, 1, 0))
+    }
+    fn attest_ownership() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn verify_attestation() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn set_metadata(l: u32) -> Weight {
+        base::<RocksDbWeight>(1, 0).saturating_add(per_byte(l))
+    }
+    fn freeze() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn thaw() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn freeze_status() -> Weight {
+        base::<RocksDbWeight>(3, 0)
+    }
+    fn act_for() -> Weight {
+        base::<RocksDbWeight>(0, 1)
+    }
+    fn acting_for() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn admin_op() -> Weight {
+        base::<RocksDbWeight>(4, 1)
+    }
+    fn confirm_admin_op() -> Weight {
+        base::<RocksDbWeight>(4, 2)
+    }
+    fn cancel_admin_op() -> Weight {
+        base::<RocksDbWeight>(3, 1)
+    }
+    fn pending_admin_op() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn admin_confirmer() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn next_free_item_id(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 1).saturating_add(per_element::<RocksDbWeight>(n, 1, 0))
+    }
+    fn mint_next(n: u32) -> Weight {
+        base::<RocksDbWeight>(3, 2).saturating_add(per_element::<RocksDbWeight>(n, 1, 0))
+    }
+    fn collections_owned(n: u32) -> Weight {
+        base::<RocksDbWeight>(1, 0).saturating_add(per_element::<RocksDbWeight>(n, 1, 0))
+    }
+    fn grant_capability(n: u32) -> Weight {
+        base::<RocksDbWeight>(2, 1).saturating_add(per_element::<RocksDbWeight>(n, 0, 0))
+    }
+    fn capabilities() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn last_error() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn publish_snapshot_root() -> Weight {
+        base::<RocksDbWeight>(0, 1)
+    }
+    fn verify_snapshot_membership(n: u32) -> Weight {
+        base::<RocksDbWeight>(1, 0).saturating_add(per_element::<RocksDbWeight>(n, 0, 0))
+    }
+    fn set_memo() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn memo() -> Weight {
+        base::<RocksDbWeight>(2, 0)
+    }
+    fn set_transfer_cooldown() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn transfer_cooldown() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn free_holding() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn submit_deferred_op() -> Weight {
+        base::<RocksDbWeight>(5, 4)
+    }
+    fn cancel_deferred_op(n: u32) -> Weight {
+        base::<RocksDbWeight>(3, 3).saturating_add(per_element::<RocksDbWeight>(n, 0, 0))
+    }
+    fn deferred_op_status() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn process_deferred_op() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn set_watcher() -> Weight {
+        base::<RocksDbWeight>(3, 3)
+    }
+    fn watcher() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn scan_watched_item() -> Weight {
+        base::<RocksDbWeight>(5, 1)
+    }
+    fn notify_watcher() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn burn() -> Weight {
+        base::<RocksDbWeight>(2, 5)
+    }
+    fn allowance() -> Weight {
+        base::<RocksDbWeight>(3, 0)
+    }
+    fn anchor_provenance() -> Weight {
+        base::<RocksDbWeight>(0, 1)
+    }
+    fn verify_provenance() -> Weight {
+        base::<RocksDbWeight>(2, 0)
+    }
+    fn bump_nonce() -> Weight {
+        base::<RocksDbWeight>(1, 1)
+    }
+    fn collection_nonce() -> Weight {
+        base::<RocksDbWeight>(1, 0)
+    }
+    fn collection_destroyed() -> Weight {
+        base::<RocksDbWeight>(2, 0)
+    }
+}